@@ -5,23 +5,38 @@ use std::path::Path;
 
 use {
     Ruleset,
+    ValidationError,
     TransformError,
     TransformedRecord,
 };
+use output::RecordNumberColumn;
+use dedup::{
+    DedupMode,
+    DedupVerdict,
+};
 
 use csv;
 
+/// Number of records to sample when inferring date formats.
+const DATE_INFERENCE_SAMPLE_SIZE: usize = 1000;
+
 /// Configuration options for the `Cli`.
 pub struct Options
 {
     /// See `CsvOptions`.
     pub csv_options: CsvOptions,
+    /// Record number column to add to the output.
+    ///
+    /// Overrides the record number column configured by the `Ruleset`. If neither is set the
+    /// default `RecordNumberColumn` is used.
+    pub record_number: Option<RecordNumberColumn>,
 }
 
 impl Default for Options {
     fn default() -> Options {
         Options {
             csv_options: Default::default(),
+            record_number: None,
         }
     }
 }
@@ -131,12 +146,51 @@ impl Cli
         }
     }
 
-    pub fn run<I: AsRef<Path>, O: AsRef<Path>, E: AsRef<Path>>(&self, input_file_path: I, output_file_name: O, error_file_name: E) {
-        let (mut reader, headers) = self.reader_from_file(input_file_path);
+    pub fn run<I: AsRef<Path>, O: AsRef<Path>, E: AsRef<Path>>(&self, input_file_path: I, output_file_name: O, error_file_name: E) -> Result<(), Vec<ValidationError>> {
+        let (mut reader, headers) = self.reader_from_file(input_file_path.as_ref());
+
+        self.ruleset.validate_rules(&headers)?;
+        let mut ruleset = self.ruleset.clone();
+        let inferred_date_formats = if ruleset.infers_date_formats() {
+            let (mut reader, _) = self.reader_from_file(input_file_path.as_ref());
+            let sample: Vec<Vec<String>> = reader.records()
+                .take(DATE_INFERENCE_SAMPLE_SIZE)
+                .filter_map(|record| record.ok())
+                .collect();
+            ruleset.infer_date_formats(&headers, &sample)
+        } else {
+            Vec::new()
+        };
+        let ruleset = ruleset;
+
+        let output_mapping = ruleset.output_mapping(&headers).map_err(|e| vec![e])?;
+        let record_number = self.record_number_column();
+        ruleset.validate_record_number_column(&headers, &record_number).map_err(|e| vec![e])?;
+        let transformed_headers = ruleset.transformed_headers(&headers);
+        let mut deduplicator = ruleset.dedup().map(|dedup| {
+            dedup.deduplicator(&transformed_headers).expect("Ruleset is not valid for input file")
+        });
+
+        let mut constraint_checker = ruleset.constraint_checker(&headers).map_err(|e| vec![e])?;
+
+        if ruleset.dedup().map(|dedup| dedup.mode()) == Some(DedupMode::KeepLast) {
+            // Keeping the last of each duplicate requires knowing which record is last before
+            // outputting any records, so make an extra pass over the file to find them.
+            let deduplicator = deduplicator.as_mut().unwrap();
+            let (mut reader, _) = self.reader_from_file(input_file_path.as_ref());
+            for (record_n, record) in reader.records().enumerate() {
+                let original_line_n = record_n + 2; // Plus one for headers and plus one for zero-indexing.
+                if let Ok(ref rec) = record {
+                    let transformed_record = ruleset.apply_rules(&headers, rec, original_line_n);
+                    deduplicator.observe(&transformed_record.field_values, original_line_n)
+                        .expect("Unable to access deduplication index");
+                }
+            }
+        }
 
         let mut output_writer = csv::Writer::from_file(output_file_name).expect("Unable to open output file for writing");
-        let mut output_headers = headers.clone();
-        output_headers.insert(0, "Record Number".to_string());
+        let mut output_headers = output_mapping.headers().to_vec();
+        record_number.insert_header(&mut output_headers);
         output_writer.encode(output_headers).expect("Unable to write to output file");
 
         let mut error_writer = csv::Writer::from_file(error_file_name).expect("Unable to open error file for writing");
@@ -148,9 +202,12 @@ impl Cli
         ];
         error_writer.encode(error_headers).expect("Unable to write to error file");
 
+        let mut n_records = 0;
+        let mut n_errors = 0;
         for (record_n, record) in reader.records().enumerate() {
+            n_records += 1;
             let original_line_n = record_n + 2; // Plus one for headers and plus one for zero-indexing.
-            let transformed_record: TransformedRecord = match record {
+            let (record, transformed_record): (Vec<String>, TransformedRecord) = match record {
                 Err(e) => {
                     let err = TransformError {
                         field_value: "".to_string(),
@@ -159,20 +216,69 @@ impl Cli
                         reason: format!("{}", e),
                     };
                     error_writer.encode(err).expect("Unable to write to error file");
+                    n_errors += 1;
                     continue;
                 },
-                Ok(ref rec) => self.ruleset.apply_rules(&headers, rec, original_line_n)
+                Ok(rec) => {
+                    let transformed_record = ruleset.apply_rules(&headers, &rec, original_line_n);
+                    (rec, transformed_record)
+                }
+            };
+            let mut errors = transformed_record.errors;
+            let verdict = match deduplicator {
+                Some(ref mut d) => d.check(&transformed_record.field_values, original_line_n)
+                    .expect("Unable to access deduplication index"),
+                None => DedupVerdict::Keep,
             };
+            match verdict {
+                DedupVerdict::Keep => {},
+                DedupVerdict::Drop => {
+                    // Still report the dropped record's errors, since its duplicate may not share them.
+                    n_errors += errors.len();
+                    for error in errors {
+                        error_writer.encode(error).expect("Unable to write to error file");
+                    }
+                    continue;
+                },
+                DedupVerdict::Report(e) => errors.push(e),
+            }
+            errors.extend(constraint_checker.check(&transformed_record.field_values, original_line_n)
+                .expect("Unable to access constraint index"));
+
             let record_fields: Vec<Option<String>> = {
-                let mut fs = vec![Some(original_line_n.to_string())];
-                fs.extend(transformed_record.field_values);
-                fs
+                let mut fs = output_mapping.apply(&transformed_record.field_values);
+                record_number.insert_value(&mut fs, &record, original_line_n);
+                match ruleset.null_value() {
+                    Some(null_value) => fs.into_iter().map(|f| f.or_else(|| Some(null_value.to_string()))).collect(),
+                    None => fs,
+                }
             };
             output_writer.encode(record_fields).expect("Unable to write to output file");
-            for error in transformed_record.errors {
+            n_errors += errors.len();
+            for error in errors {
                 error_writer.encode(error).expect("Unable to write to error file");
             }
         }
+
+        info!("Processed {} records with {} errors", n_records, n_errors);
+        if let Some(ref d) = deduplicator {
+            info!("Found {} duplicate records", d.n_duplicates());
+        }
+        for (field_name, formats) in inferred_date_formats {
+            if formats.is_empty() {
+                warn!("Unable to infer a date format for field {}", field_name);
+            } else {
+                let formats: Vec<&str> = formats.iter().map(|f| f.as_str()).collect();
+                info!("Inferred date formats for field {}: {}", field_name, formats.join(", "));
+            }
+        }
+        Ok(())
+    }
+
+    fn record_number_column(&self) -> RecordNumberColumn {
+        self.options.record_number.clone()
+            .or_else(|| self.ruleset.record_number_column().cloned())
+            .unwrap_or_default()
     }
 
     fn reader_from_file<P: AsRef<Path>>(&self, path: P) -> (csv::Reader<File>, Vec<String>) {
@@ -0,0 +1,154 @@
+//! Constraints on the values of fields across all of the records of a CSV file.
+
+use transformer::TransformError;
+use key_index::{
+    self,
+    KeyIndex,
+};
+
+use std::io;
+
+/// Kind of a `Constraint`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum ConstraintKind {
+    /// The combined values of the fields must be unique across the file.
+    ///
+    /// Records where every field of the constraint is empty are not checked.
+    Unique,
+    /// The combined values of the fields must be unique across the file and none of the fields
+    /// may be empty.
+    PrimaryKey,
+}
+
+fn default_max_memory_keys() -> usize {
+    key_index::DEFAULT_MAX_MEMORY_KEYS
+}
+
+/// A constraint on the values of one or more transformed fields across all records.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Constraint {
+    field_names: Vec<String>,
+    kind: ConstraintKind,
+    /// Number of keys to keep in memory before spilling the constraint's index to disk.
+    #[serde(default = "default_max_memory_keys")]
+    max_memory_keys: usize,
+}
+
+impl Constraint {
+    /// Construct a new `Constraint` that the values of the specified fields are unique.
+    pub fn unique(field_names: &[&str]) -> Constraint {
+        Self::new(field_names, ConstraintKind::Unique)
+    }
+
+    /// Construct a new `Constraint` that the values of the specified fields are unique and not
+    /// empty.
+    pub fn primary_key(field_names: &[&str]) -> Constraint {
+        Self::new(field_names, ConstraintKind::PrimaryKey)
+    }
+
+    fn new(field_names: &[&str], kind: ConstraintKind) -> Constraint {
+        Constraint {
+            field_names: field_names.iter().map(|s| s.to_string()).collect(),
+            kind: kind,
+            max_memory_keys: default_max_memory_keys(),
+        }
+    }
+
+    /// Names of the fields this constraint applies to.
+    pub fn field_names(&self) -> &[String] {
+        &self.field_names
+    }
+}
+
+struct ConstraintState {
+    field_ns: Vec<usize>,
+    field_names: String,
+    kind: ConstraintKind,
+    index: KeyIndex,
+}
+
+/// Stateful checking of `Constraint`s over the transformed records of a single CSV file.
+///
+/// # Examples
+/// ```
+/// use csv_sanity::constraint::{
+///     Constraint,
+///     ConstraintChecker,
+/// };
+///
+/// let headers = vec!["id".to_string(), "email".to_string()];
+/// let constraints = vec![Constraint::primary_key(&["id"])];
+/// let mut checker = ConstraintChecker::new(&constraints, &headers).unwrap();
+/// let first = vec![Some("1".to_string()), Some("jsnow@example.com".to_string())];
+/// let second = vec![Some("1".to_string()), Some("asnow@example.com".to_string())];
+/// assert!(checker.check(&first, 2).unwrap().is_empty());
+/// let errors = checker.check(&second, 3).unwrap();
+/// assert_eq!(errors[0].record_n, 3);
+/// assert_eq!(errors[0].reason, "duplicate of value in record 2");
+/// ```
+pub struct ConstraintChecker {
+    states: Vec<ConstraintState>,
+}
+
+impl ConstraintChecker {
+    /// Construct a `ConstraintChecker` for records with the specified (transformed) headers.
+    ///
+    /// Returns the names of any constrained fields that are missing from the headers on failure.
+    pub fn new(constraints: &[Constraint], headers: &[String]) -> Result<ConstraintChecker, Vec<String>> {
+        let missing_fields: Vec<String> = constraints.iter()
+            .flat_map(|c| c.field_names.iter())
+            .filter(|name| !headers.contains(name))
+            .cloned()
+            .collect();
+        if !missing_fields.is_empty() {
+            return Err(missing_fields);
+        }
+        Ok(ConstraintChecker {
+            states: constraints.iter().map(|constraint| {
+                ConstraintState {
+                    field_ns: constraint.field_names.iter()
+                        .map(|name| headers.iter().position(|h| h == name).unwrap())
+                        .collect(),
+                    field_names: constraint.field_names.join(", "),
+                    kind: constraint.kind,
+                    index: KeyIndex::new(constraint.max_memory_keys),
+                }
+            }).collect(),
+        })
+    }
+
+    /// Check a transformed record against every constraint, returning any violations.
+    pub fn check(&mut self, field_values: &[Option<String>], record_n: usize) -> io::Result<Vec<TransformError>> {
+        let mut errors = Vec::new();
+        for state in self.states.iter_mut() {
+            let key: Vec<Option<&str>> = state.field_ns.iter()
+                .map(|field_n| field_values.get(*field_n).and_then(|fv| fv.as_ref().map(|v| v.as_str())))
+                .collect();
+            let key_value = key.iter().map(|k| k.unwrap_or("")).collect::<Vec<&str>>().join(", ");
+            if state.kind == ConstraintKind::PrimaryKey && key.iter().any(|k| k.is_none()) {
+                errors.push(TransformError {
+                    field_value: key_value,
+                    field_name: state.field_names.clone(),
+                    record_n: record_n,
+                    reason: "primary key must not be empty".to_string(),
+                });
+                continue;
+            }
+            if key.iter().all(|k| k.is_none()) {
+                continue;
+            }
+
+            let key_hash = state.index.hash_key(&key);
+            match state.index.get(key_hash)? {
+                Some(first_record_n) => errors.push(TransformError {
+                    field_value: key_value,
+                    field_name: state.field_names.clone(),
+                    record_n: record_n,
+                    reason: format!("duplicate of value in record {}", first_record_n),
+                }),
+                None => state.index.insert(key_hash, record_n)?,
+            }
+        }
+        Ok(errors)
+    }
+}
@@ -0,0 +1,201 @@
+//! Detection and removal of duplicate records.
+
+use transformer::TransformError;
+use key_index::{
+    self,
+    KeyIndex,
+};
+
+use std::io;
+
+/// What to do with records whose key has already been seen.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum DedupMode {
+    /// Output the first record with a key and drop the rest.
+    KeepFirst,
+    /// Output the last record with a key and drop the rest.
+    ///
+    /// Requires an extra pass over the input file to find the last record for each key.
+    KeepLast,
+    /// Output every record, reporting an error for each duplicate that references the record
+    /// number of the first record with the same key.
+    Report,
+}
+
+impl Default for DedupMode {
+    fn default() -> DedupMode {
+        DedupMode::KeepFirst
+    }
+}
+
+fn default_max_memory_keys() -> usize {
+    key_index::DEFAULT_MAX_MEMORY_KEYS
+}
+
+/// Configuration for deduplicating records by the values of one or more transformed fields.
+///
+/// Records whose key fields are all empty are never considered duplicates.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Dedup {
+    field_names: Vec<String>,
+    #[serde(default)]
+    mode: DedupMode,
+    /// Number of keys to keep in memory before spilling the key index to disk.
+    #[serde(default = "default_max_memory_keys")]
+    max_memory_keys: usize,
+}
+
+impl Dedup {
+    /// Construct a new `Dedup` keyed on the fields with the specified names.
+    pub fn new(field_names: &[&str], mode: DedupMode) -> Dedup {
+        Dedup {
+            field_names: field_names.iter().map(|s| s.to_string()).collect(),
+            mode: mode,
+            max_memory_keys: default_max_memory_keys(),
+        }
+    }
+
+    /// Limit the number of keys kept in memory before spilling the key index to disk.
+    pub fn with_max_memory_keys(self, max_memory_keys: usize) -> Dedup {
+        Dedup {
+            max_memory_keys: max_memory_keys,
+            .. self
+        }
+    }
+
+    pub fn mode(&self) -> DedupMode {
+        self.mode
+    }
+
+    /// Names of the fields making up the key of a record.
+    pub fn field_names(&self) -> &[String] {
+        &self.field_names
+    }
+
+    /// Construct a `Deduplicator` for records with the specified (transformed) headers.
+    ///
+    /// Returns the names of any key fields that are missing from the headers on failure.
+    pub fn deduplicator(&self, headers: &[String]) -> Result<Deduplicator, Vec<String>> {
+        let missing_fields: Vec<String> = self.field_names.iter()
+            .filter(|name| !headers.contains(name))
+            .cloned()
+            .collect();
+        if !missing_fields.is_empty() {
+            return Err(missing_fields);
+        }
+        Ok(Deduplicator {
+            field_ns: self.field_names.iter()
+                .map(|name| headers.iter().position(|h| h == name).unwrap())
+                .collect(),
+            field_names: self.field_names.join(", "),
+            mode: self.mode,
+            index: KeyIndex::new(self.max_memory_keys),
+            n_duplicates: 0,
+        })
+    }
+}
+
+/// What to do with a record after checking it for duplicates.
+#[derive(PartialEq, Eq, Debug)]
+pub enum DedupVerdict {
+    /// Output the record.
+    Keep,
+    /// Drop the record from the output.
+    Drop,
+    /// Output the record and report it as a duplicate.
+    Report(TransformError),
+}
+
+/// Stateful duplicate detection over the records of a single CSV file.
+///
+/// # Examples
+/// ```
+/// use csv_sanity::dedup::{
+///     Dedup,
+///     DedupMode,
+///     DedupVerdict,
+/// };
+///
+/// let headers = vec!["id".to_string(), "email".to_string()];
+/// let mut deduplicator = Dedup::new(&["email"], DedupMode::KeepFirst).deduplicator(&headers).unwrap();
+/// let first = vec![Some("1".to_string()), Some("jsnow@example.com".to_string())];
+/// let second = vec![Some("2".to_string()), Some("jsnow@example.com".to_string())];
+/// assert_eq!(deduplicator.check(&first, 2).unwrap(), DedupVerdict::Keep);
+/// assert_eq!(deduplicator.check(&second, 3).unwrap(), DedupVerdict::Drop);
+/// assert_eq!(deduplicator.n_duplicates(), 1);
+/// ```
+pub struct Deduplicator {
+    field_ns: Vec<usize>,
+    field_names: String,
+    mode: DedupMode,
+    index: KeyIndex,
+    n_duplicates: usize,
+}
+
+impl Deduplicator {
+    fn key<'a>(&self, field_values: &'a [Option<String>]) -> Option<Vec<Option<&'a str>>> {
+        let key: Vec<Option<&str>> = self.field_ns.iter()
+            .map(|field_n| field_values.get(*field_n).and_then(|fv| fv.as_ref().map(|v| v.as_str())))
+            .collect();
+        if key.iter().all(|k| k.is_none()) {
+            None
+        } else {
+            Some(key)
+        }
+    }
+
+    /// Observe a record ahead of checking it, for modes that need to see every record first.
+    ///
+    /// Only `DedupMode::KeepLast` requires records to be observed, in a separate pass over the
+    /// file before any records are checked.
+    pub fn observe(&mut self, field_values: &[Option<String>], record_n: usize) -> io::Result<()> {
+        if self.mode != DedupMode::KeepLast {
+            return Ok(());
+        }
+        if let Some(key) = self.key(field_values) {
+            let key_hash = self.index.hash_key(&key);
+            self.index.insert(key_hash, record_n)?;
+        }
+        Ok(())
+    }
+
+    /// Check whether a record is a duplicate of a previous record.
+    pub fn check(&mut self, field_values: &[Option<String>], record_n: usize) -> io::Result<DedupVerdict> {
+        let key = match self.key(field_values) {
+            Some(k) => k,
+            None => return Ok(DedupVerdict::Keep),
+        };
+        let key_hash = self.index.hash_key(&key);
+        let verdict = match self.mode {
+            DedupMode::KeepLast => match self.index.get(key_hash)? {
+                Some(last_record_n) if last_record_n != record_n => DedupVerdict::Drop,
+                _ => DedupVerdict::Keep,
+            },
+            DedupMode::KeepFirst | DedupMode::Report => match self.index.get(key_hash)? {
+                Some(first_record_n) => if self.mode == DedupMode::Report {
+                    DedupVerdict::Report(TransformError {
+                        field_value: key.iter().map(|k| k.unwrap_or("")).collect::<Vec<&str>>().join(", "),
+                        field_name: self.field_names.clone(),
+                        record_n: record_n,
+                        reason: format!("duplicate of record {}", first_record_n),
+                    })
+                } else {
+                    DedupVerdict::Drop
+                },
+                None => {
+                    self.index.insert(key_hash, record_n)?;
+                    DedupVerdict::Keep
+                }
+            },
+        };
+        if verdict != DedupVerdict::Keep {
+            self.n_duplicates += 1;
+        }
+        Ok(verdict)
+    }
+
+    /// Number of duplicate records found so far.
+    pub fn n_duplicates(&self) -> usize {
+        self.n_duplicates
+    }
+}
@@ -0,0 +1,191 @@
+//! An index of record keys that spills to disk once it grows too large to keep in memory.
+
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{
+    BuildHasher,
+    Hash,
+    Hasher,
+};
+use std::fs::File;
+use std::io::{
+    self,
+    BufWriter,
+    Read,
+    Seek,
+    SeekFrom,
+    Write,
+};
+
+use tempfile;
+
+/// A 128-bit hash identifying a key.
+///
+/// Keys are identified by their hash rather than their value so that every entry in the index has
+/// a fixed size. The hash is built from two independently seeded 64-bit hashes, so the chance of
+/// two distinct keys colliding is negligible even for billions of keys.
+pub type KeyHash = (u64, u64);
+
+/// Number of bytes of an entry in a run file: the two halves of the key hash and the record
+/// number.
+const ENTRY_SIZE: u64 = 24;
+
+/// Default number of keys kept in memory before spilling an index to disk.
+pub const DEFAULT_MAX_MEMORY_KEYS: usize = 4_000_000;
+
+/// Bits of bloom filter per key in a run, giving a false positive rate of roughly 1%.
+const BLOOM_BITS_PER_KEY: usize = 10;
+const BLOOM_HASHES: u64 = 7;
+
+/// Computes `KeyHash`es for the keys of an index.
+pub struct KeyHasher
+{
+    states: (RandomState, RandomState),
+}
+
+impl KeyHasher
+{
+    pub fn new() -> KeyHasher {
+        KeyHasher {
+            states: (RandomState::new(), RandomState::new()),
+        }
+    }
+
+    /// Hash a key made up of one or more optional field values.
+    pub fn hash_key(&self, key: &[Option<&str>]) -> KeyHash {
+        let mut first = self.states.0.build_hasher();
+        let mut second = self.states.1.build_hasher();
+        key.hash(&mut first);
+        key.hash(&mut second);
+        (first.finish(), second.finish())
+    }
+}
+
+/// A sorted run of entries spilled to a temporary file.
+struct Run
+{
+    file: File,
+    len: u64,
+    bloom: Vec<u64>,
+}
+
+impl Run
+{
+    fn write(entries: &mut Vec<(KeyHash, usize)>) -> io::Result<Run> {
+        entries.sort();
+        let n_bits = (entries.len() * BLOOM_BITS_PER_KEY).max(64);
+        let mut bloom = vec![0u64; (n_bits + 63) / 64];
+        let mut writer = BufWriter::new(tempfile::tempfile()?);
+        for &(key, record_n) in entries.iter() {
+            for bit in bloom_bits(key, bloom.len() as u64 * 64) {
+                bloom[(bit / 64) as usize] |= 1 << (bit % 64);
+            }
+            writer.write_all(&encode_u64(key.0))?;
+            writer.write_all(&encode_u64(key.1))?;
+            writer.write_all(&encode_u64(record_n as u64))?;
+        }
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        Ok(Run {
+            file: file,
+            len: entries.len() as u64,
+            bloom: bloom,
+        })
+    }
+
+    fn may_contain(&self, key: KeyHash) -> bool {
+        bloom_bits(key, self.bloom.len() as u64 * 64)
+            .all(|bit| self.bloom[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+
+    fn get(&mut self, key: KeyHash) -> io::Result<Option<usize>> {
+        if !self.may_contain(key) {
+            return Ok(None);
+        }
+        let (mut low, mut high) = (0, self.len);
+        let mut buf = [0u8; ENTRY_SIZE as usize];
+        while low < high {
+            let mid = low + (high - low) / 2;
+            self.file.seek(SeekFrom::Start(mid * ENTRY_SIZE))?;
+            self.file.read_exact(&mut buf)?;
+            let entry_key = (decode_u64(&buf[0..8]), decode_u64(&buf[8..16]));
+            if entry_key == key {
+                return Ok(Some(decode_u64(&buf[16..24]) as usize));
+            } else if entry_key < key {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn bloom_bits(key: KeyHash, n_bits: u64) -> impl Iterator<Item=u64> {
+    (0..BLOOM_HASHES).map(move |i| key.0.wrapping_add(i.wrapping_mul(key.1)) % n_bits)
+}
+
+fn encode_u64(n: u64) -> [u8; 8] {
+    let mut bytes = [0u8; 8];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = (n >> (56 - i * 8)) as u8;
+    }
+    bytes
+}
+
+fn decode_u64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |n, byte| (n << 8) | *byte as u64)
+}
+
+/// An index from keys to record numbers.
+///
+/// Entries are kept in memory until there are `max_memory_keys` of them, at which point they are
+/// sorted and written to a temporary file. Lookups check memory first and then the spilled runs
+/// from newest to oldest, so the most recently inserted record number for a key is returned.
+/// Each run keeps a bloom filter in memory so most lookups of keys that aren't in a run never
+/// touch the disk.
+pub struct KeyIndex
+{
+    hasher: KeyHasher,
+    memory: HashMap<KeyHash, usize>,
+    max_memory_keys: usize,
+    runs: Vec<Run>,
+}
+
+impl KeyIndex
+{
+    pub fn new(max_memory_keys: usize) -> KeyIndex {
+        KeyIndex {
+            hasher: KeyHasher::new(),
+            memory: HashMap::new(),
+            max_memory_keys: max_memory_keys.max(1),
+            runs: Vec::new(),
+        }
+    }
+
+    pub fn hash_key(&self, key: &[Option<&str>]) -> KeyHash {
+        self.hasher.hash_key(key)
+    }
+
+    /// Record number most recently inserted for a key, if any.
+    pub fn get(&mut self, key: KeyHash) -> io::Result<Option<usize>> {
+        if let Some(record_n) = self.memory.get(&key) {
+            return Ok(Some(*record_n));
+        }
+        for run in self.runs.iter_mut().rev() {
+            if let Some(record_n) = run.get(key)? {
+                return Ok(Some(record_n));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Insert a record number for a key, shadowing any previously inserted record number.
+    pub fn insert(&mut self, key: KeyHash, record_n: usize) -> io::Result<()> {
+        self.memory.insert(key, record_n);
+        if self.memory.len() >= self.max_memory_keys {
+            let mut entries: Vec<(KeyHash, usize)> = self.memory.drain().collect();
+            self.runs.push(Run::write(&mut entries)?);
+        }
+        Ok(())
+    }
+}
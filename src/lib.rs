@@ -6,13 +6,20 @@ extern crate regex;
 #[macro_use]
 extern crate lazy_static;
 extern crate unicode_segmentation; 
-extern crate time;
+extern crate unicode_normalization;
+extern crate chrono;
+extern crate chrono_tz;
+extern crate phonenumber;
+extern crate idna;
 extern crate csv;
 #[macro_use]
 extern crate custom_derive;
 #[macro_use]
 extern crate newtype_derive;
 extern crate rustc_serialize;
+#[macro_use]
+extern crate log;
+extern crate tempfile;
 
 mod newtypes;
 
@@ -26,11 +33,37 @@ pub use transformer::{
 
 pub mod transformers;
 
+pub mod splitter;
+pub use splitter::{
+    Splitter,
+    SplitResult,
+};
+
+pub mod splitters;
+
+pub mod validator;
+pub use validator::Validator;
+
+pub mod validators;
+
+mod template;
+
 mod ruleset;
 pub use ruleset::{
     Rule,
+    SplitRule,
+    CombineRule,
     Ruleset,
     TransformedRecord,
+    ValidationError,
 };
 
+pub mod output;
+
+mod key_index;
+
+pub mod dedup;
+
+pub mod constraint;
+
 pub mod cli;
@@ -7,10 +7,16 @@ extern crate regex;
 #[macro_use]
 extern crate clap;
 
+use csv_sanity::Ruleset;
 use csv_sanity::cli::{
     self,
     Cli,
 };
+use csv_sanity::output::{
+    RecordNumberColumn,
+    RecordNumberPosition,
+    RecordNumberValue,
+};
 
 use std::fs::File;
 use std::path::Path;
@@ -75,6 +81,20 @@ fn main() {
             .short("r")
             .long("ruleset")
             .takes_value(true))
+        .arg(Arg::with_name("record_number_name")
+            .help("Header of the record number column. Defaults to \"Record Number\"")
+            .long("record_number_name")
+            .takes_value(true))
+        .arg(Arg::with_name("record_number_position")
+            .help("Position of the record number column in the output, or omit to leave it out. Defaults to start")
+            .long("record_number_position")
+            .possible_values(&["start", "end", "omit"])
+            .takes_value(true))
+        .arg(Arg::with_name("record_number_value")
+            .help("Value of the record number column: the input line number or a stable hash of the original record. Defaults to line")
+            .long("record_number_value")
+            .possible_values(&["line", "hash"])
+            .takes_value(true))
         .get_matches();
 
     let ruleset_file_path = Path::new(matches.value_of("ruleset").unwrap_or("ruleset.json"));
@@ -82,26 +102,61 @@ fn main() {
         Ok(f) => f,
         Err(e) => exit_with_error(&format!("unable to read ruleset file {}: {}", ruleset_file_path.display(), e))
     };
-    let ruleset = match serde_json::from_reader(ruleset_file) {
+    let ruleset: Ruleset = match serde_json::from_reader(ruleset_file) {
         Ok(r) => r,
         Err(e) => {
             exit_with_error(&format!("failed to parse ruleset from {}: {}", ruleset_file_path.display(), e));
         }
     };
 
+    let record_number = record_number_from_matches(&matches, ruleset.record_number_column());
+
     let cli_app = Cli::new_with_options(ruleset, cli::Options {
         csv_options: cli::CsvOptions {
             delimiter: b'\t',
             .. Default::default()
         },
-        .. Default::default()
+        record_number: record_number,
     });
 
     // NOTE: Required arguments are validated by clap, so we should be safe to use expect here.
     let input_file_name = matches.value_of("INPUT_FILE").expect("INPUT_FILE argument could not be found!");
     let output_file_name = matches.value_of("output_file").unwrap_or("output.csv");
     let error_file_name = matches.value_of("error_file").unwrap_or("errors.csv");
-    cli_app.run(input_file_name, output_file_name, error_file_name);
+    if let Err(errors) = cli_app.run(input_file_name, output_file_name, error_file_name) {
+        let reasons: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        exit_with_error(&format!("ruleset {} is not valid for input file {}: {}",
+                                 ruleset_file_path.display(), input_file_name, reasons.join("; ")));
+    }
+}
+
+/// Build the record number column from the command line arguments, starting from the ruleset's
+/// configuration. Returns `None` if no record number arguments were given.
+fn record_number_from_matches(matches: &clap::ArgMatches, ruleset_record_number: Option<&RecordNumberColumn>) -> Option<RecordNumberColumn> {
+    let name = matches.value_of("record_number_name");
+    let position = matches.value_of("record_number_position");
+    let value = matches.value_of("record_number_value");
+    if name.is_none() && position.is_none() && value.is_none() {
+        return None;
+    }
+
+    let mut record_number = ruleset_record_number.cloned().unwrap_or_default();
+    if let Some(name) = name {
+        record_number.name = name.to_string();
+    }
+    // NOTE: Possible values are validated by clap, so anything else is unreachable.
+    match position {
+        Some("start") => record_number.position = RecordNumberPosition::Start,
+        Some("end") => record_number.position = RecordNumberPosition::End,
+        Some("omit") => record_number.position = RecordNumberPosition::Omit,
+        _ => {},
+    }
+    match value {
+        Some("line") => record_number.value = RecordNumberValue::LineNumber,
+        Some("hash") => record_number.value = RecordNumberValue::Hash,
+        _ => {},
+    }
+    Some(record_number)
 }
 
 fn exit_with_error(error_msg: &str) -> !
@@ -0,0 +1,311 @@
+//! Types that control how transformed records are written to the output file.
+
+/// Position of the record number column in the output.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum RecordNumberPosition {
+    /// Insert the column before all other columns.
+    Start,
+    /// Append the column after all other columns.
+    End,
+    /// Do not output a record number column at all.
+    Omit,
+}
+
+impl Default for RecordNumberPosition {
+    fn default() -> RecordNumberPosition {
+        RecordNumberPosition::Start
+    }
+}
+
+/// Value written to the record number column for each record.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum RecordNumberValue {
+    /// The line number of the record in the input file.
+    LineNumber,
+    /// A stable hash of the record's original, untransformed fields.
+    ///
+    /// Unlike the line number the hash does not change when records are added, removed or
+    /// reordered in the input file.
+    Hash,
+}
+
+impl Default for RecordNumberValue {
+    fn default() -> RecordNumberValue {
+        RecordNumberValue::LineNumber
+    }
+}
+
+fn default_record_number_name() -> String {
+    "Record Number".to_string()
+}
+
+/// Configuration of the record number column that is added to the output.
+///
+/// `RecordNumberColumn` implements `Default` with the following defaults:
+///
+/// ```
+/// use csv_sanity::output::{
+///     RecordNumberColumn,
+///     RecordNumberPosition,
+///     RecordNumberValue,
+/// };
+///
+/// let defaults = RecordNumberColumn {
+///     name: "Record Number".to_string(),
+///     position: RecordNumberPosition::Start,
+///     value: RecordNumberValue::LineNumber,
+/// };
+/// assert_eq!(defaults, Default::default());
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct RecordNumberColumn {
+    /// Header of the record number column.
+    #[serde(default = "default_record_number_name")]
+    pub name: String,
+    /// See `RecordNumberPosition`.
+    #[serde(default)]
+    pub position: RecordNumberPosition,
+    /// See `RecordNumberValue`.
+    #[serde(default)]
+    pub value: RecordNumberValue,
+}
+
+impl Default for RecordNumberColumn {
+    fn default() -> RecordNumberColumn {
+        RecordNumberColumn {
+            name: default_record_number_name(),
+            position: Default::default(),
+            value: Default::default(),
+        }
+    }
+}
+
+impl RecordNumberColumn {
+    /// Construct a `RecordNumberColumn` that is omitted from the output.
+    pub fn omitted() -> RecordNumberColumn {
+        RecordNumberColumn {
+            position: RecordNumberPosition::Omit,
+            .. Default::default()
+        }
+    }
+
+    /// Add the record number column to the output headers.
+    ///
+    /// ```
+    /// use csv_sanity::output::{
+    ///     RecordNumberColumn,
+    ///     RecordNumberPosition,
+    /// };
+    ///
+    /// let column = RecordNumberColumn {
+    ///     name: "Row".to_string(),
+    ///     position: RecordNumberPosition::End,
+    ///     .. Default::default()
+    /// };
+    /// let mut headers = vec!["Id".to_string(), "Email".to_string()];
+    /// column.insert_header(&mut headers);
+    /// assert_eq!(headers, vec!["Id", "Email", "Row"]);
+    /// ```
+    pub fn insert_header(&self, headers: &mut Vec<String>) {
+        self.insert(headers, self.name.clone());
+    }
+
+    /// Add the record number column to a transformed record's fields.
+    ///
+    /// `original_fields` are the fields of the record as read from the input file and are only
+    /// used when the column value is `RecordNumberValue::Hash`.
+    pub fn insert_value(&self, field_values: &mut Vec<Option<String>>, original_fields: &[String], record_n: usize) {
+        let value = match self.value {
+            RecordNumberValue::LineNumber => record_n.to_string(),
+            RecordNumberValue::Hash => record_hash(original_fields),
+        };
+        self.insert(field_values, Some(value));
+    }
+
+    fn insert<T>(&self, values: &mut Vec<T>, value: T) {
+        match self.position {
+            RecordNumberPosition::Start => values.insert(0, value),
+            RecordNumberPosition::End => values.push(value),
+            RecordNumberPosition::Omit => {},
+        }
+    }
+}
+
+/// Compute a stable hash of a record's fields as a 16 digit hexadecimal string.
+///
+/// The hash is a 64-bit FNV-1a hash, which is stable across platforms and releases of
+/// csv-sanity. Each field is prefixed with its length so that moving characters between adjacent
+/// fields changes the hash.
+///
+/// ```
+/// use csv_sanity::output::record_hash;
+///
+/// let a = record_hash(&["John".to_string(), "Snow".to_string()]);
+/// let b = record_hash(&["Joh".to_string(), "nSnow".to_string()]);
+/// assert_eq!(a.len(), 16);
+/// assert_eq!(a, record_hash(&["John".to_string(), "Snow".to_string()]));
+/// assert!(a != b);
+/// ```
+pub fn record_hash(fields: &[String]) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    {
+        let mut write = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+        for field in fields {
+            let len = field.len() as u64;
+            let len_bytes: Vec<u8> = (0..8).map(|i| (len >> (i * 8)) as u8).collect();
+            write(&len_bytes);
+            write(field.as_bytes());
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// A column of the output file.
+///
+/// When a `Ruleset` defines its output columns, the output contains exactly those columns in the
+/// order they are defined. Fields of the input file that aren't referenced by an `OutputColumn`
+/// are dropped from the output.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub enum OutputColumn {
+    /// A transformed field from the input file, optionally output under a different header.
+    Field {
+        field_name: String,
+        #[serde(default, skip_serializing_if="Option::is_none")]
+        header: Option<String>,
+    },
+    /// A column with the same value for every record.
+    Constant {
+        header: String,
+        value: String,
+    },
+}
+
+impl OutputColumn {
+    /// Construct an `OutputColumn` that outputs a field under its original header.
+    pub fn field(field_name: &str) -> OutputColumn {
+        OutputColumn::Field {
+            field_name: field_name.to_string(),
+            header: None,
+        }
+    }
+
+    /// Construct an `OutputColumn` that outputs a field under a new header.
+    pub fn renamed_field(field_name: &str, header: &str) -> OutputColumn {
+        OutputColumn::Field {
+            field_name: field_name.to_string(),
+            header: Some(header.to_string()),
+        }
+    }
+
+    /// Construct an `OutputColumn` with a constant value.
+    pub fn constant(header: &str, value: &str) -> OutputColumn {
+        OutputColumn::Constant {
+            header: header.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    /// Header of this column in the output file.
+    pub fn header(&self) -> &str {
+        match *self {
+            OutputColumn::Field { ref header, ref field_name } => header.as_ref().unwrap_or(field_name),
+            OutputColumn::Constant { ref header, .. } => header,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum OutputSource {
+    Field(usize),
+    Constant(String),
+}
+
+/// Output columns resolved against the headers of a CSV file.
+///
+/// # Examples
+/// ```
+/// use csv_sanity::output::{
+///     OutputColumn,
+///     OutputMapping,
+/// };
+///
+/// let headers: Vec<String> = vec!["id", "fname", "internal"].iter().map(|s| s.to_string()).collect();
+/// let mapping = OutputMapping::new(&[
+///     OutputColumn::renamed_field("fname", "First Name"),
+///     OutputColumn::field("id"),
+///     OutputColumn::constant("source", "legacy_export"),
+/// ], &headers).unwrap();
+/// assert_eq!(mapping.headers(), &["First Name", "id", "source"]);
+///
+/// let field_values = vec![Some("1".to_string()), Some("John".to_string()), None];
+/// assert_eq!(mapping.apply(&field_values), vec![
+///     Some("John".to_string()),
+///     Some("1".to_string()),
+///     Some("legacy_export".to_string()),
+/// ]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct OutputMapping {
+    headers: Vec<String>,
+    sources: Vec<OutputSource>,
+}
+
+impl OutputMapping {
+    /// Construct an `OutputMapping` that outputs every field under its original header.
+    pub fn identity(headers: &[String]) -> OutputMapping {
+        OutputMapping {
+            headers: headers.to_vec(),
+            sources: (0..headers.len()).map(OutputSource::Field).collect(),
+        }
+    }
+
+    /// Resolve `OutputColumn`s against the headers of a CSV file.
+    ///
+    /// Returns the names of any referenced fields that are missing from the headers on failure.
+    pub fn new(columns: &[OutputColumn], headers: &[String]) -> Result<OutputMapping, Vec<String>> {
+        let mut missing_fields = Vec::new();
+        let mut sources = Vec::new();
+        for column in columns {
+            match *column {
+                OutputColumn::Field { ref field_name, .. } => {
+                    match headers.iter().position(|h| h == field_name) {
+                        Some(field_n) => sources.push(OutputSource::Field(field_n)),
+                        None => missing_fields.push(field_name.clone()),
+                    }
+                },
+                OutputColumn::Constant { ref value, .. } => sources.push(OutputSource::Constant(value.clone())),
+            }
+        }
+        if missing_fields.is_empty() {
+            Ok(OutputMapping {
+                headers: columns.iter().map(|c| c.header().to_string()).collect(),
+                sources: sources,
+            })
+        } else {
+            Err(missing_fields)
+        }
+    }
+
+    /// Headers of the output file.
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// Map a transformed record's fields to the output columns.
+    ///
+    /// Fields missing from the end of a short record are output as empty values.
+    pub fn apply(&self, field_values: &[Option<String>]) -> Vec<Option<String>> {
+        self.sources.iter().map(|source| match *source {
+            OutputSource::Field(field_n) => field_values.get(field_n).cloned().unwrap_or(None),
+            OutputSource::Constant(ref value) => Some(value.clone()),
+        }).collect()
+    }
+}
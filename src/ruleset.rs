@@ -2,11 +2,31 @@ use Transformer;
 use transformer::{
     TransformResult,
     TransformError,
+    InputRecord,
 };
 use transformers::{
     Transformers,
     TrimTransformer,
     NoneTransformer,
+    DateFormat,
+};
+use Splitter;
+use splitter::SplitResult;
+use splitters::Splitters;
+use template;
+use Validator;
+use validator::Record;
+use validators::Validators;
+use dedup::Dedup;
+use constraint::{
+    Constraint,
+    ConstraintChecker,
+};
+use output::{
+    RecordNumberColumn,
+    RecordNumberPosition,
+    OutputColumn,
+    OutputMapping,
 };
 
 use std::hash::{
@@ -20,6 +40,7 @@ use std::collections::{
     HashSet,
 };
 use std::error;
+use std::mem;
 use std::fmt::{
     self,
     Formatter,
@@ -153,16 +174,196 @@ impl Rule
     /// rule.apply(field, field_name, 1);
     /// ```
     pub fn apply(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
+        self.apply_in_record(field_value, field_name, &InputRecord::new(&[], &[]), record_n)
+    }
+
+    /// Apply this rule to a field of a record, giving transformers that depend on other fields
+    /// access to the record as read from the input file.
+    pub fn apply_in_record(&self, field_value: &str, field_name: &str, record: &InputRecord, record_n: usize) -> TransformResult {
         // XXX: Does the applicability check belong inside the apply method? Or should the caller
         //   decide?
         match self.applicability {
-            Applicability::Global => self.transformer.transform(field_value, field_name, record_n),
+            Applicability::Global => self.transformer.transform_in_record(field_value, field_name, record, record_n),
             Applicability::Fields { ref field_names } if field_names.contains(&field_name.to_string()) => {
-                self.transformer.transform(field_value, field_name, record_n)
+                self.transformer.transform_in_record(field_value, field_name, record, record_n)
             },
             _ => Ok(Some(field_value.to_string()))
         }
     }
+
+    fn is_applicable(&self, field_name: &str) -> bool {
+        match self.applicability {
+            Applicability::Global => true,
+            Applicability::Fields { ref field_names } => field_names.contains(field_name),
+        }
+    }
+}
+
+/// A `Splitter` applied to a single CSV record's field, producing several new fields.
+///
+/// The fields produced by a `SplitRule` are appended to the record after the fields of the input
+/// file, and `Rule`s are applied to them like any other field.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SplitRule
+{
+    field_name: String,
+    splitter: Splitters,
+}
+
+impl SplitRule
+{
+    /// Construct a new `SplitRule` that splits the field with the specified name.
+    ///
+    /// # Examples
+    /// ```
+    /// use csv_sanity::SplitRule;
+    /// use csv_sanity::splitters::*;
+    ///
+    /// let rule = SplitRule::new("Full Name", Splitters::Delimiter(
+    ///     DelimiterSplitter::new(" ", &["First Name", "Last Name"])
+    /// ));
+    /// assert_eq!(rule.field_names(), vec!["First Name", "Last Name"]);
+    /// ```
+    pub fn new(field_name: &str, splitter: Splitters) -> SplitRule {
+        SplitRule {
+            field_name: field_name.to_string(),
+            splitter: splitter,
+        }
+    }
+
+    /// Name of the field that this rule splits.
+    pub fn source_field_name(&self) -> &str {
+        &self.field_name
+    }
+
+    /// Names of the fields produced by this rule.
+    pub fn field_names(&self) -> Vec<String> {
+        self.splitter.field_names()
+    }
+
+    /// Apply this rule to the value of the field it splits, returning the resulting `SplitResult`.
+    pub fn apply(&self, field_value: &str, record_n: usize) -> SplitResult {
+        self.splitter.split(field_value, &self.field_name, record_n)
+    }
+}
+
+/// A template combining several of a CSV record's fields into a single field.
+///
+/// `CombineRule`s are applied after all `Rule`s have been applied to a record, in the order they
+/// were added to the `Ruleset`. The template references fields by name using the same `$name`
+/// and `${name}` syntax as `RegexTransformer`. The combined value is written to a new field, or
+/// replaces an existing field of the same name, and is then passed through the rule's
+/// transformers, if any.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct CombineRule
+{
+    field_name: String,
+    template: String,
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    transformers: Vec<Transformers>,
+}
+
+impl CombineRule
+{
+    /// Construct a new `CombineRule` that writes the expanded template to the field with the
+    /// specified name.
+    ///
+    /// # Examples
+    /// ```
+    /// use csv_sanity::CombineRule;
+    ///
+    /// let rule = CombineRule::new("Address", "$Address1 ${Address 2}");
+    /// assert_eq!(rule.template_field_names(), vec!["Address1", "Address 2"]);
+    /// ```
+    pub fn new(field_name: &str, template: &str) -> CombineRule {
+        Self::with_transformers(field_name, template, Vec::new())
+    }
+
+    /// Construct a new `CombineRule` whose combined value is passed through one or more
+    /// transformers.
+    ///
+    /// # Examples
+    /// ```
+    /// use csv_sanity::CombineRule;
+    /// use csv_sanity::transformers::*;
+    ///
+    /// let rule = CombineRule::with_transformers("Signup Date", "$Year-$Month-$Day", vec![
+    ///     Transformers::Date(DateTransformer::with_iso8601_output(vec!["%Y-%m-%d".to_string()])),
+    /// ]);
+    /// ```
+    pub fn with_transformers(field_name: &str, template: &str, transformers: Vec<Transformers>) -> CombineRule {
+        CombineRule {
+            field_name: field_name.to_string(),
+            template: template.to_string(),
+            transformers: transformers,
+        }
+    }
+
+    /// Name of the field that this rule writes to.
+    pub fn field_name(&self) -> &str {
+        &self.field_name
+    }
+
+    /// Names of the fields referenced by this rule's template.
+    pub fn template_field_names(&self) -> Vec<String> {
+        template::field_names(&self.template)
+    }
+
+    /// Apply this rule to a transformed record, returning the resulting `TransformResult`.
+    ///
+    /// If every field referenced by the template is empty, the result is empty as well.
+    ///
+    /// # Examples
+    /// ```
+    /// use csv_sanity::CombineRule;
+    ///
+    /// let rule = CombineRule::new("Date", "$Year-$Month-$Day");
+    /// let headers = vec!["Year", "Month", "Day"].iter().map(|s| s.to_string()).collect();
+    /// let field_values = vec![Some("2017".to_string()), Some("11".to_string()), Some("22".to_string())];
+    /// assert_eq!(rule.apply(&headers, &field_values, 1), Ok(Some("2017-11-22".to_string())));
+    /// ```
+    pub fn apply(&self, headers: &Vec<String>, field_values: &Vec<Option<String>>, record_n: usize) -> TransformResult {
+        self.apply_in_record(headers, field_values, &InputRecord::new(&[], &[]), record_n)
+    }
+
+    /// Apply this rule to a transformed record, giving transformers that depend on other fields
+    /// access to the record as read from the input file.
+    ///
+    /// # Examples
+    /// ```
+    /// use csv_sanity::CombineRule;
+    /// use csv_sanity::transformer::InputRecord;
+    /// use csv_sanity::transformers::*;
+    ///
+    /// let rule = CombineRule::with_transformers("Postal Code", "$Zip", vec![
+    ///     Transformers::PostalCode(PostalCodeTransformer::for_country("US").with_country_field("Country")),
+    /// ]);
+    /// let headers: Vec<String> = vec!["Country", "Zip"].iter().map(|s| s.to_string()).collect();
+    /// let fields: Vec<String> = vec!["CA", "k1a0b1"].iter().map(|s| s.to_string()).collect();
+    /// let field_values = fields.iter().map(|f| Some(f.clone())).collect();
+    /// let record = InputRecord::new(&headers, &fields);
+    /// assert_eq!(rule.apply_in_record(&headers, &field_values, &record, 1), Ok(Some("K1A 0B1".to_string())));
+    /// ```
+    pub fn apply_in_record(&self, headers: &Vec<String>, field_values: &Vec<Option<String>>, record: &InputRecord, record_n: usize) -> TransformResult {
+        let lookup = |name: &str| {
+            headers.iter().position(|h| h == name)
+                .and_then(|field_n| field_values.get(field_n))
+                .and_then(|fv| fv.as_ref().map(|v| v.as_str()))
+        };
+        let field_names = self.template_field_names();
+        if !field_names.is_empty() && field_names.iter().all(|name| lookup(name).is_none()) {
+            return Ok(None);
+        }
+
+        let mut value = Some(template::expand(&self.template, lookup));
+        for transformer in self.transformers.iter() {
+            value = match value {
+                Some(ref v) => transformer.transform_in_record(v, &self.field_name, record, record_n)?,
+                None => break,
+            };
+        }
+        Ok(value)
+    }
 }
 
 impl Ord for Rule
@@ -209,7 +410,23 @@ impl PartialOrd for Rule
 /// ```
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Ruleset {
-    rules: BinaryHeap<Rule>
+    rules: BinaryHeap<Rule>,
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    splits: Vec<SplitRule>,
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    combinations: Vec<CombineRule>,
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    validators: Vec<Validators>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    dedup: Option<Dedup>,
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    constraints: Vec<Constraint>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    record_number: Option<RecordNumberColumn>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    output_columns: Option<Vec<OutputColumn>>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    null_value: Option<String>,
 }
 
 impl Ruleset {
@@ -229,7 +446,15 @@ impl Ruleset {
     /// Construct a new `Ruleset` without any of the default rules.
     pub fn without_default_rules() -> Ruleset {
         Ruleset {
-            rules: BinaryHeap::new()
+            rules: BinaryHeap::new(),
+            splits: Vec::new(),
+            combinations: Vec::new(),
+            validators: Vec::new(),
+            dedup: None,
+            constraints: Vec::new(),
+            record_number: None,
+            output_columns: None,
+            null_value: None,
         }
     }
 
@@ -238,12 +463,368 @@ impl Ruleset {
         self.rules.push(rule);
     }
 
+    /// Add a `SplitRule` to this ruleset.
+    ///
+    /// Errors transforming the fields produced by the split are reported against the field that
+    /// was split.
+    ///
+    /// # Examples
+    /// ```
+    /// use csv_sanity::{
+    ///     Ruleset,
+    ///     Rule,
+    ///     SplitRule,
+    /// };
+    /// use csv_sanity::splitters::*;
+    /// use csv_sanity::transformers::*;
+    ///
+    /// let mut ruleset = Ruleset::new();
+    /// ruleset.add_split(SplitRule::new("Location", Splitters::Delimiter(
+    ///     DelimiterSplitter::new(", ", &["City", "State"])
+    /// )));
+    /// ruleset.add_rule(Rule::for_fields(&["State"], Transformers::State(StateTransformer::new())));
+    /// let headers = vec!["Location"].iter().map(|s| s.to_string()).collect();
+    /// let record = vec!["Springfield, Nowhere"].iter().map(|s| s.to_string()).collect();
+    /// let transformed_record = ruleset.apply_rules(&headers, &record, 1);
+    /// assert_eq!(transformed_record.errors[0].field_name, "Location");
+    /// assert_eq!(transformed_record.errors[0].field_value, "Nowhere");
+    /// ```
+    pub fn add_split(&mut self, split: SplitRule) {
+        self.splits.push(split);
+    }
+
+    /// Add a `CombineRule` to this ruleset.
+    pub fn add_combination(&mut self, combination: CombineRule) {
+        self.combinations.push(combination);
+    }
+
+    /// Add a `Validator` to this ruleset.
+    ///
+    /// Validators are applied to the whole record after all rules and combinations have been
+    /// applied.
+    ///
+    /// # Examples
+    /// ```
+    /// use csv_sanity::Ruleset;
+    /// use csv_sanity::validators::*;
+    ///
+    /// let mut ruleset = Ruleset::new();
+    /// ruleset.add_validator(Validators::Compare(
+    ///     CompareValidator::new("end_date", Operator::GreaterThan, "start_date", Comparison::Text)
+    /// ));
+    /// let headers = vec!["start_date", "end_date"].iter().map(|s| s.to_string()).collect();
+    /// let record = vec!["2017-11-22", "2017-11-01"].iter().map(|s| s.to_string()).collect();
+    /// let transformed_record = ruleset.apply_rules(&headers, &record, 1);
+    /// assert_eq!(transformed_record.errors.len(), 2);
+    /// assert_eq!(transformed_record.errors[0].reason, "end_date must be greater than start_date");
+    /// ```
+    pub fn add_validator(&mut self, validator: Validators) {
+        self.validators.push(validator);
+    }
+
+    /// Headers of the records produced by this ruleset from a CSV file with the specified headers.
+    ///
+    /// These are the headers of the CSV file followed by the fields produced by any `SplitRule`s
+    /// and any new fields written by `CombineRule`s.
+    ///
+    /// # Examples
+    /// ```
+    /// use csv_sanity::{
+    ///     Ruleset,
+    ///     SplitRule,
+    /// };
+    /// use csv_sanity::splitters::*;
+    ///
+    /// let mut ruleset = Ruleset::new();
+    /// ruleset.add_split(SplitRule::new("Full Name", Splitters::Delimiter(
+    ///     DelimiterSplitter::new(" ", &["First Name", "Last Name"])
+    /// )));
+    /// let headers = vec!["Id", "Full Name"].iter().map(|s| s.to_string()).collect();
+    /// assert_eq!(ruleset.transformed_headers(&headers), vec!["Id", "Full Name", "First Name", "Last Name"]);
+    /// ```
+    pub fn transformed_headers(&self, headers: &Vec<String>) -> Vec<String> {
+        let mut transformed_headers = headers.clone();
+        for split in self.splits.iter() {
+            transformed_headers.extend(split.field_names());
+        }
+        for combination in self.combinations.iter() {
+            if !transformed_headers.contains(&combination.field_name) {
+                transformed_headers.push(combination.field_name.clone());
+            }
+        }
+        transformed_headers
+    }
+
+    /// The deduplication of records configured by this ruleset, if any.
+    pub fn dedup(&self) -> Option<&Dedup> {
+        self.dedup.as_ref()
+    }
+
+    /// Deduplicate records by the values of one or more transformed fields. See `Dedup`.
+    pub fn set_dedup(&mut self, dedup: Dedup) {
+        self.dedup = Some(dedup);
+    }
+
+    /// Add a `Constraint` on the values of fields across all records.
+    pub fn add_constraint(&mut self, constraint: Constraint) {
+        self.constraints.push(constraint);
+    }
+
+    /// Construct a `ConstraintChecker` for this ruleset's constraints against the headers of a CSV
+    /// file.
+    ///
+    /// Constraints are stateful, so a new `ConstraintChecker` is needed for each CSV file.
+    pub fn constraint_checker(&self, headers: &Vec<String>) -> Result<ConstraintChecker, ValidationError> {
+        ConstraintChecker::new(&self.constraints, &self.transformed_headers(headers)).map_err(|missing_fields| {
+            ValidationError {
+                reason: format!("The following constraint fields were not found in headers: '{:?}'", missing_fields),
+            }
+        })
+    }
+
+    /// The record number column configured by this ruleset, if any.
+    pub fn record_number_column(&self) -> Option<&RecordNumberColumn> {
+        self.record_number.as_ref()
+    }
+
+    /// Configure the record number column that is added to the output.
+    pub fn set_record_number_column(&mut self, record_number: RecordNumberColumn) {
+        self.record_number = Some(record_number);
+    }
+
+    /// The value written to the output for empty fields, if not an empty string.
+    pub fn null_value(&self) -> Option<&str> {
+        self.null_value.as_ref().map(|s| s.as_str())
+    }
+
+    /// Write empty fields to the output as the specified value, e.g. `\N` or `NULL`, rather than
+    /// as an empty string.
+    pub fn set_null_value(&mut self, null_value: &str) {
+        self.null_value = Some(null_value.to_string());
+    }
+
+    /// Define the columns of the output, replacing the default of outputting every field under its
+    /// original header. See `OutputColumn`.
+    pub fn set_output_columns(&mut self, output_columns: Vec<OutputColumn>) {
+        self.output_columns = Some(output_columns);
+    }
+
+    /// Resolve this ruleset's output columns against the headers of a CSV file.
+    ///
+    /// Output columns may reference the fields produced by `SplitRule`s as well as the fields of
+    /// the CSV file.
+    ///
+    /// # Examples
+    /// ```
+    /// use csv_sanity::Ruleset;
+    /// use csv_sanity::output::OutputColumn;
+    ///
+    /// let mut ruleset = Ruleset::new();
+    /// ruleset.set_output_columns(vec![
+    ///     OutputColumn::renamed_field("fname", "First Name"),
+    ///     OutputColumn::constant("source", "legacy_export"),
+    /// ]);
+    ///
+    /// let headers = vec!["id", "fname"].iter().map(|s| s.to_string()).collect();
+    /// let mapping = ruleset.output_mapping(&headers).unwrap();
+    /// assert_eq!(mapping.headers(), &["First Name", "source"]);
+    ///
+    /// let headers = vec!["id", "first_name"].iter().map(|s| s.to_string()).collect();
+    /// assert!(ruleset.output_mapping(&headers).is_err());
+    ///
+    /// ruleset.set_output_columns(vec![
+    ///     OutputColumn::renamed_field("fname", "Name"),
+    ///     OutputColumn::renamed_field("lname", "Name"),
+    /// ]);
+    /// let headers = vec!["fname", "lname"].iter().map(|s| s.to_string()).collect();
+    /// assert!(ruleset.output_mapping(&headers).is_err());
+    /// ```
+    pub fn output_mapping(&self, headers: &Vec<String>) -> Result<OutputMapping, ValidationError> {
+        if let Some(ref output_columns) = self.output_columns {
+            let mut seen = HashSet::new();
+            let duplicates: Vec<&str> = output_columns.iter().map(|c| c.header()).filter(|h| !seen.insert(*h)).collect();
+            if !duplicates.is_empty() {
+                return Err(ValidationError {
+                    reason: format!("The following output column headers are duplicated: '{:?}'", duplicates),
+                });
+            }
+        }
+        match self.output_columns {
+            Some(ref output_columns) => OutputMapping::new(output_columns, &self.transformed_headers(headers)).map_err(|missing_fields| {
+                ValidationError {
+                    reason: format!("The following output columns were not found in headers: '{:?}'", missing_fields),
+                }
+            }),
+            None => Ok(OutputMapping::identity(&self.transformed_headers(headers))),
+        }
+    }
+
+    /// Check that a record number column doesn't share its header with one of the output columns.
+    ///
+    /// # Examples
+    /// ```
+    /// use csv_sanity::Ruleset;
+    /// use csv_sanity::output::RecordNumberColumn;
+    ///
+    /// let ruleset = Ruleset::new();
+    /// let headers = vec!["ID", "Email"].iter().map(|s| s.to_string()).collect();
+    /// assert!(ruleset.validate_record_number_column(&headers, &RecordNumberColumn::default()).is_ok());
+    ///
+    /// let record_number = RecordNumberColumn {
+    ///     name: "ID".to_string(),
+    ///     .. Default::default()
+    /// };
+    /// assert!(ruleset.validate_record_number_column(&headers, &record_number).is_err());
+    /// assert!(ruleset.validate_record_number_column(&headers, &RecordNumberColumn {
+    ///     name: "ID".to_string(),
+    ///     .. RecordNumberColumn::omitted()
+    /// }).is_ok());
+    /// ```
+    pub fn validate_record_number_column(&self, headers: &Vec<String>, record_number: &RecordNumberColumn) -> Result<(), ValidationError> {
+        if record_number.position == RecordNumberPosition::Omit {
+            return Ok(());
+        }
+        if self.output_mapping(headers)?.headers().contains(&record_number.name) {
+            return Err(ValidationError {
+                reason: format!("The following record number column conflicts with an output column: '{}'", record_number.name),
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether any `DateTransformer`s in this ruleset infer their input formats, requiring a call
+    /// to `infer_date_formats` before applying the rules.
+    pub fn infers_date_formats(&self) -> bool {
+        self.rules.iter().any(|rule| match rule.transformer {
+            Transformers::Date(ref t) => t.infers_formats(),
+            _ => false,
+        })
+    }
+
+    /// Infer the input formats of `DateTransformer`s that infer their input formats, for each of
+    /// the CSV file's fields they apply to, from a sample of the file's records.
+    ///
+    /// The sampled values have the rules that come before the `DateTransformer` applied to them
+    /// first. Returns the names of the fields and the formats inferred for them.
+    ///
+    /// Formats are only inferred for the fields of the CSV file, so `validate_rules` rejects
+    /// rulesets that infer the formats of fields produced by `SplitRule`s or `CombineRule`s.
+    ///
+    /// # Examples
+    /// ```
+    /// use csv_sanity::{
+    ///     Ruleset,
+    ///     Rule,
+    ///     SplitRule,
+    /// };
+    /// use csv_sanity::splitters::*;
+    /// use csv_sanity::transformers::*;
+    ///
+    /// let mut ruleset = Ruleset::new();
+    /// ruleset.add_rule(Rule::for_fields(&["Start", "End"], Transformers::Date(
+    ///     DateTransformer::with_iso8601_output(Vec::new()).with_format_inference(false)
+    /// )));
+    /// let headers: Vec<String> = vec!["Start", "Period"].iter().map(|s| s.to_string()).collect();
+    /// let sample = vec![vec!["25/12/2017".to_string(), "".to_string()]];
+    /// assert_eq!(ruleset.infer_date_formats(&headers, &sample)[0].0, "Start");
+    ///
+    /// ruleset.add_split(SplitRule::new("Period", Splitters::Delimiter(
+    ///     DelimiterSplitter::new(" - ", &["End"])
+    /// )));
+    /// assert!(ruleset.validate_rules(&headers).is_err());
+    /// ```
+    pub fn infer_date_formats(&mut self, headers: &[String], sample: &[Vec<String>]) -> Vec<(String, Vec<DateFormat>)> {
+        let mut rules = mem::replace(&mut self.rules, BinaryHeap::new()).into_vec();
+        let mut inferred = Vec::new();
+        for rule_n in 0..rules.len() {
+            let field_names: Vec<&String> = match rules[rule_n].transformer {
+                Transformers::Date(ref t) if t.infers_formats() => {
+                    headers.iter().filter(|h| rules[rule_n].is_applicable(h)).collect()
+                },
+                _ => continue,
+            };
+            for field_name in field_names {
+                let field_n = headers.iter().position(|h| h == field_name).unwrap();
+                let values: Vec<String> = sample.iter()
+                    .filter_map(|record| record.get(field_n).map(|value| (InputRecord::new(headers, record), value)))
+                    .filter_map(|(record, value)| {
+                        transform_with_rules(rules[..rule_n].iter(), value, field_name, &record, 0, &mut Vec::new())
+                    })
+                    .collect();
+                let values: Vec<&str> = values.iter().map(|v| v.as_str()).collect();
+                if let Transformers::Date(ref mut t) = rules[rule_n].transformer {
+                    let formats = t.infer_input_formats(&values);
+                    t.set_inferred_formats(field_name, formats.clone());
+                    inferred.push((field_name.clone(), formats));
+                }
+            }
+        }
+        // The rules are still in heap order, so rebuilding the heap leaves them in the same order.
+        self.rules = BinaryHeap::from(rules);
+        inferred
+    }
+
     /// Validate this ruleset against a CSV file by comparing it's `Rule`s against the headers.
     pub fn validate_rules(&self, headers: &Vec<String>) -> Result<(), Vec<ValidationError>> {
         let mut errors = Vec::new();
+        let transformed_headers = self.transformed_headers(headers);
+        let header_set = HashSet::<String>::from_iter(transformed_headers.clone());
+        if header_set.len() < transformed_headers.len() {
+            let mut seen = HashSet::new();
+            let duplicates: Vec<&String> = transformed_headers.iter().filter(|h| !seen.insert(*h)).collect();
+            errors.push(
+                ValidationError {
+                    reason: format!("The following split fields conflict with existing fields: '{:?}'", duplicates),
+                }
+            )
+        }
+        for split in self.splits.iter() {
+            if !headers.contains(&split.field_name) {
+                errors.push(
+                    ValidationError {
+                        reason: format!("The following split field was not found in headers: '{}'", split.field_name),
+                    }
+                )
+            }
+        }
+        for combination in self.combinations.iter() {
+            let missing_fields: Vec<String> = combination.template_field_names().into_iter()
+                .filter(|name| !header_set.contains(name))
+                .collect();
+            if !missing_fields.is_empty() {
+                errors.push(
+                    ValidationError {
+                        reason: format!("The following template fields were not found in headers: '{:?}'", missing_fields),
+                    }
+                )
+            }
+        }
+        for validator in self.validators.iter() {
+            let missing_fields: Vec<String> = validator.field_names().into_iter()
+                .filter(|name| !header_set.contains(name))
+                .collect();
+            if !missing_fields.is_empty() {
+                errors.push(
+                    ValidationError {
+                        reason: format!("The following validator fields were not found in headers: '{:?}'", missing_fields),
+                    }
+                )
+            }
+        }
+        if let Some(ref dedup) = self.dedup {
+            if let Err(missing_fields) = dedup.deduplicator(&transformed_headers) {
+                errors.push(
+                    ValidationError {
+                        reason: format!("The following dedup fields were not found in headers: '{:?}'", missing_fields),
+                    }
+                )
+            }
+        }
+        if let Err(e) = self.constraint_checker(headers) {
+            errors.push(e);
+        }
         for rule in self.rules.iter() {
             if let Applicability::Fields { ref field_names } = rule.applicability {
-                let header_set = HashSet::<String>::from_iter(headers.clone());
                 let field_set = HashSet::<String>::from_iter(field_names.clone());
                 let diff: HashSet<String> = field_set.difference(&header_set).cloned().collect();
                 if diff.len() > 0 {
@@ -257,6 +838,57 @@ impl Ruleset {
                 }
             }
         }
+        for rule in self.rules.iter() {
+            let infers_date_formats = match rule.transformer {
+                Transformers::Date(ref t) => t.infers_formats(),
+                _ => false,
+            };
+            if infers_date_formats {
+                // Formats are only inferred from the fields of the input file.
+                let uninferred_fields: Vec<&String> = transformed_headers.iter()
+                    .filter(|h| !headers.contains(h) && rule.is_applicable(h))
+                    .collect();
+                if !uninferred_fields.is_empty() {
+                    errors.push(
+                        ValidationError {
+                            reason: format!("The following fields are not in the input file so their date formats can't be inferred: '{:?}'", uninferred_fields),
+                        }
+                    )
+                }
+            }
+        }
+        for combination in self.combinations.iter() {
+            let infers_date_formats = combination.transformers.iter().any(|transformer| match *transformer {
+                Transformers::Date(ref t) => t.infers_formats(),
+                _ => false,
+            });
+            if infers_date_formats {
+                errors.push(
+                    ValidationError {
+                        reason: format!("The following combined field's date formats can't be inferred: '{}'", combination.field_name),
+                    }
+                )
+            }
+        }
+        let transformers = self.rules.iter().map(|rule| &rule.transformer)
+            .chain(self.combinations.iter().flat_map(|combination| combination.transformers.iter()));
+        for transformer in transformers {
+            if let Transformers::PostalCode(ref t) = *transformer {
+                match t.country_field() {
+                    Some(country_field) if !headers.iter().any(|h| h == country_field) => {
+                        errors.push(
+                            ValidationError {
+                                reason: format!("The following postal code country field was not found in headers: '{}'", country_field),
+                            }
+                        )
+                    },
+                    _ => {},
+                }
+            }
+        }
+        if let Err(e) = self.output_mapping(headers) {
+            errors.push(e);
+        }
         if errors.is_empty() {
             Ok(())
         } else {
@@ -265,34 +897,19 @@ impl Ruleset {
     }
 
     /// Apply this `Ruleset` to a record from a CSV file.
+    ///
+    /// The fields of the resulting `TransformedRecord` correspond to the headers returned by
+    /// `Ruleset::transformed_headers`.
     pub fn apply_rules(&self, headers: &Vec<String>, fields: &Vec<String>, record_n: usize) -> TransformedRecord {
         let expected_n_fields = headers.len();
+        let input_record = InputRecord::new(headers, fields);
 
         let mut errors: Vec<TransformError> = Vec::new();
         let mut transformed_fields: Vec<Option<String>> = Vec::new();
         for (field_n, field_value) in fields.iter().enumerate() {
             if field_n < expected_n_fields {
                 let field_name = &headers[field_n];
-                let mut transformed_field_value = Some(field_value.clone());
-                // Try each rule in order of priority and test to see if it is applicable.
-                for rule in self.rules.iter() {
-                    let new_value = match transformed_field_value {
-                        Some(ref fv) => {
-                            let transform_result = rule.apply(fv, &field_name, record_n);
-                            match transform_result {
-                                Ok(tfv) => tfv,
-                                Err(e) => {
-                                    errors.push(e);
-                                    None
-                                }
-                            }
-                        },
-                        // The last transformer returned None, so we can short circuit and just
-                        // return None for the field value.
-                        None => break
-                    };
-                    transformed_field_value = new_value;
-                }
+                let transformed_field_value = self.transform_field(field_value, field_name, &input_record, record_n, &mut errors);
                 transformed_fields.insert(field_n, transformed_field_value);
             } else {
                 errors.push(
@@ -306,11 +923,101 @@ impl Ruleset {
             }
         }
 
+        if !self.splits.is_empty() {
+            // Split fields are positioned after all of the header fields, so pad out short records.
+            transformed_fields.resize(expected_n_fields, None);
+            for split in self.splits.iter() {
+                let field_names = split.field_names();
+                let source_value = headers.iter()
+                    .position(|h| h == &split.field_name)
+                    .and_then(|field_n| transformed_fields[field_n].clone());
+                let split_values = match source_value {
+                    Some(ref sv) => match split.apply(sv, record_n) {
+                        Ok(values) => values,
+                        Err(e) => {
+                            errors.push(e);
+                            Vec::new()
+                        }
+                    },
+                    None => Vec::new(),
+                };
+                for (field_n, field_name) in field_names.iter().enumerate() {
+                    let mut split_errors = Vec::new();
+                    let transformed_field_value = match split_values.get(field_n) {
+                        Some(&Some(ref fv)) => self.transform_field(fv, field_name, &input_record, record_n, &mut split_errors),
+                        _ => None,
+                    };
+                    // Attribute errors to the field that was split, since the split fields aren't
+                    // in the input file.
+                    errors.extend(split_errors.into_iter().map(|e| TransformError {
+                        field_name: split.field_name.clone(),
+                        .. e
+                    }));
+                    transformed_fields.push(transformed_field_value);
+                }
+            }
+        }
+
+        if !self.combinations.is_empty() {
+            let transformed_headers = self.transformed_headers(headers);
+            // Fields written by combinations that aren't already present start out empty.
+            transformed_fields.resize(transformed_headers.len(), None);
+            for combination in self.combinations.iter() {
+                let value = match combination.apply_in_record(&transformed_headers, &transformed_fields, &input_record, record_n) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        errors.push(e);
+                        None
+                    }
+                };
+                if let Some(field_n) = transformed_headers.iter().position(|h| h == &combination.field_name) {
+                    transformed_fields[field_n] = value;
+                }
+            }
+        }
+
+        if !self.validators.is_empty() {
+            let transformed_headers = self.transformed_headers(headers);
+            let record = Record::new(&transformed_headers, &transformed_fields);
+            for validator in self.validators.iter() {
+                errors.extend(validator.validate(&record, record_n));
+            }
+        }
+
         TransformedRecord {
             field_values: transformed_fields,
             errors: errors,
         }
     }
+
+    fn transform_field(&self, field_value: &str, field_name: &str, record: &InputRecord, record_n: usize, errors: &mut Vec<TransformError>) -> Option<String> {
+        transform_with_rules(self.rules.iter(), field_value, field_name, record, record_n, errors)
+    }
+}
+
+fn transform_with_rules<'a, I>(rules: I, field_value: &str, field_name: &str, record: &InputRecord, record_n: usize, errors: &mut Vec<TransformError>) -> Option<String>
+    where I: Iterator<Item=&'a Rule> {
+    let mut transformed_field_value = Some(field_value.to_string());
+    // Try each rule in order of priority and test to see if it is applicable.
+    for rule in rules {
+        let new_value = match transformed_field_value {
+            Some(ref fv) => {
+                let transform_result = rule.apply_in_record(fv, field_name, record, record_n);
+                match transform_result {
+                    Ok(tfv) => tfv,
+                    Err(e) => {
+                        errors.push(e);
+                        None
+                    }
+                }
+            },
+            // The last transformer returned None, so we can short circuit and just
+            // return None for the field value.
+            None => break
+        };
+        transformed_field_value = new_value;
+    }
+    transformed_field_value
 }
 
 /// Error for when a `Ruleset` does not validate against a CSV file.
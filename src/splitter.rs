@@ -0,0 +1,19 @@
+//! Traits and types that define splitting a CSV record's field into several fields.
+
+use std::result;
+
+use transformer::TransformError;
+
+/// `Result` for splitting a CSV record's field, either the values of the fields produced by the
+/// split or a `TransformError` if unsuccessful.
+///
+/// The values are in the same order as the field names returned by `Splitter::field_names`.
+pub type SplitResult = result::Result<Vec<Option<String>>, TransformError>;
+
+pub trait Splitter
+{
+    /// Names of the fields produced by this splitter.
+    fn field_names(&self) -> Vec<String>;
+
+    fn split(&self, field_value: &str, field_name: &str, record_n: usize) -> SplitResult;
+}
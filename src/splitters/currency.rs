@@ -0,0 +1,54 @@
+use Splitter;
+use splitter::SplitResult;
+use transformer::TransformError;
+use transformers::CurrencyFormat;
+
+/// Split an amount of money into an amount field and an ISO 4217 currency code field.
+///
+/// ```
+/// use csv_sanity::Splitter;
+/// use csv_sanity::splitters::CurrencySplitter;
+/// use csv_sanity::transformers::CurrencyFormat;
+///
+/// let splitter = CurrencySplitter::new("Amount", "Currency", CurrencyFormat::new());
+/// assert_eq!(splitter.split("$1,234.5", "Revenue", 1),
+///     Ok(vec![Some("1234.50".to_string()), Some("USD".to_string())]));
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct CurrencySplitter
+{
+    amount_field_name: String,
+    currency_field_name: String,
+    #[serde(flatten)]
+    format: CurrencyFormat,
+}
+
+impl CurrencySplitter
+{
+    pub fn new(amount_field_name: &str, currency_field_name: &str, format: CurrencyFormat) -> CurrencySplitter {
+        CurrencySplitter {
+            amount_field_name: amount_field_name.to_string(),
+            currency_field_name: currency_field_name.to_string(),
+            format: format,
+        }
+    }
+}
+
+impl Splitter for CurrencySplitter
+{
+    fn field_names(&self) -> Vec<String> {
+        vec![self.amount_field_name.clone(), self.currency_field_name.clone()]
+    }
+
+    fn split(&self, field_value: &str, field_name: &str, record_n: usize) -> SplitResult {
+        match self.format.parse(field_value) {
+            Ok(money) => Ok(vec![Some(money.amount), Some(money.currency)]),
+            Err(reason) => Err(TransformError {
+                field_value: field_value.to_string(),
+                field_name: field_name.to_string(),
+                record_n: record_n,
+                reason: reason,
+            }),
+        }
+    }
+}
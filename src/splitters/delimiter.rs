@@ -0,0 +1,35 @@
+use Splitter;
+use splitter::SplitResult;
+
+/// Split a field on a delimiter into a fixed list of fields.
+///
+/// The last field receives the remainder of the value, delimiters included. If the value has
+/// fewer parts than there are fields, the remaining fields are empty.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct DelimiterSplitter
+{
+    delimiter: String,
+    field_names: Vec<String>,
+}
+
+impl DelimiterSplitter
+{
+    pub fn new(delimiter: &str, field_names: &[&str]) -> DelimiterSplitter {
+        DelimiterSplitter {
+            delimiter: delimiter.to_string(),
+            field_names: field_names.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl Splitter for DelimiterSplitter
+{
+    fn field_names(&self) -> Vec<String> {
+        self.field_names.clone()
+    }
+
+    fn split(&self, field_value: &str, _: &str, _: usize) -> SplitResult {
+        let mut parts = field_value.splitn(self.field_names.len(), self.delimiter.as_str());
+        Ok(self.field_names.iter().map(|_| parts.next().map(|p| p.to_string())).collect())
+    }
+}
@@ -0,0 +1,71 @@
+use Splitter;
+use splitter::SplitResult;
+use transformer::TransformError;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct FixedWidthColumn
+{
+    field_name: String,
+    width: usize,
+}
+
+impl FixedWidthColumn
+{
+    pub fn new(field_name: &str, width: usize) -> FixedWidthColumn {
+        FixedWidthColumn {
+            field_name: field_name.to_string(),
+            width: width,
+        }
+    }
+}
+
+/// Split a field into fields of fixed widths, measured in characters.
+///
+/// If the value is too short to fill every column the remaining fields are empty. Values longer
+/// than the combined width of the columns are rejected.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct FixedWidthSplitter
+{
+    columns: Vec<FixedWidthColumn>,
+}
+
+impl FixedWidthSplitter
+{
+    pub fn new(columns: Vec<FixedWidthColumn>) -> FixedWidthSplitter {
+        FixedWidthSplitter {
+            columns: columns,
+        }
+    }
+}
+
+impl Splitter for FixedWidthSplitter
+{
+    fn field_names(&self) -> Vec<String> {
+        self.columns.iter().map(|c| c.field_name.clone()).collect()
+    }
+
+    fn split(&self, field_value: &str, field_name: &str, record_n: usize) -> SplitResult {
+        let total_width: usize = self.columns.iter().map(|c| c.width).sum();
+        let chars: Vec<char> = field_value.chars().collect();
+        if chars.len() > total_width {
+            return Err(TransformError {
+                field_value: field_value.to_string(),
+                field_name: field_name.to_string(),
+                record_n: record_n,
+                reason: format!("longer than {} characters", total_width),
+            });
+        }
+
+        let mut start = 0;
+        Ok(self.columns.iter().map(|column| {
+            let end = (start + column.width).min(chars.len());
+            let value = if start < end {
+                Some(chars[start..end].iter().collect())
+            } else {
+                None
+            };
+            start = end;
+            value
+        }).collect())
+    }
+}
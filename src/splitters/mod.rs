@@ -0,0 +1,52 @@
+use splitter::{
+    Splitter,
+    SplitResult,
+};
+
+mod regex;
+pub use self::regex::RegexSplitter;
+
+mod delimiter;
+pub use self::delimiter::DelimiterSplitter;
+
+mod fixed_width;
+pub use self::fixed_width::{
+    FixedWidthSplitter,
+    FixedWidthColumn,
+};
+
+mod currency;
+pub use self::currency::CurrencySplitter;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub enum Splitters
+{
+    Regex(RegexSplitter),
+    Delimiter(DelimiterSplitter),
+    FixedWidth(FixedWidthSplitter),
+    Currency(CurrencySplitter),
+}
+
+impl Splitter for Splitters {
+    fn field_names(&self) -> Vec<String> {
+        use self::Splitters::*;
+
+        match *self {
+            Regex(ref s) => s.field_names(),
+            Delimiter(ref s) => s.field_names(),
+            FixedWidth(ref s) => s.field_names(),
+            Currency(ref s) => s.field_names(),
+        }
+    }
+
+    fn split(&self, field_value: &str, field_name: &str, record_n: usize) -> SplitResult {
+        use self::Splitters::*;
+
+        match *self {
+            Regex(ref s) => s.split(field_value, field_name, record_n),
+            Delimiter(ref s) => s.split(field_value, field_name, record_n),
+            FixedWidth(ref s) => s.split(field_value, field_name, record_n),
+            Currency(ref s) => s.split(field_value, field_name, record_n),
+        }
+    }
+}
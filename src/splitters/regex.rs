@@ -0,0 +1,47 @@
+use Splitter;
+use splitter::SplitResult;
+use transformer::TransformError;
+use newtypes::Regex;
+
+use regex;
+
+/// Split a field into one field per named capture group of a regex.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct RegexSplitter
+{
+    regex: Regex,
+}
+
+impl RegexSplitter
+{
+    pub fn new(regex: regex::Regex) -> RegexSplitter {
+        RegexSplitter {
+            regex: Regex::from(regex),
+        }
+    }
+}
+
+impl Splitter for RegexSplitter
+{
+    fn field_names(&self) -> Vec<String> {
+        self.regex.capture_names()
+            .filter_map(|name| name.map(|n| n.to_string()))
+            .collect()
+    }
+
+    fn split(&self, field_value: &str, field_name: &str, record_n: usize) -> SplitResult {
+        if let Some(captures) = self.regex.captures(field_value) {
+            Ok(self.regex.capture_names()
+                .filter_map(|name| name)
+                .map(|name| captures.name(name).map(|m| m.as_str().to_string()))
+                .collect())
+        } else {
+            Err(TransformError {
+                field_value: field_value.to_string(),
+                field_name: field_name.to_string(),
+                record_n: record_n,
+                reason: format!("did not match pattern {}", self.regex),
+            })
+        }
+    }
+}
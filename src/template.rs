@@ -0,0 +1,72 @@
+//! Expansion of templates referencing fields by name.
+//!
+//! Templates use the same `$name` syntax as `regex::Captures::expand`: `$name` references the
+//! field with the longest name made up of letters, digits and underscores, `${name}` references a
+//! field whose name contains any other characters, and `$$` is a literal `$`.
+
+enum Part<'t> {
+    Literal(&'t str),
+    Field(&'t str),
+}
+
+/// Split a template into its literal and field reference parts.
+fn parse<'t>(template: &'t str) -> Vec<Part<'t>> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(i) = rest.find('$') {
+        if i > 0 {
+            parts.push(Part::Literal(&rest[..i]));
+        }
+        rest = &rest[i + 1..];
+        if rest.starts_with('$') {
+            parts.push(Part::Literal("$"));
+            rest = &rest[1..];
+        } else if rest.starts_with('{') && rest.contains('}') {
+            let end = rest.find('}').unwrap();
+            parts.push(Part::Field(&rest[1..end]));
+            rest = &rest[end + 1..];
+        } else {
+            let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+            if end == 0 {
+                // A lone `$` isn't a reference, so keep it as is.
+                parts.push(Part::Literal("$"));
+            } else {
+                parts.push(Part::Field(&rest[..end]));
+            }
+            rest = &rest[end..];
+        }
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Literal(rest));
+    }
+    parts
+}
+
+/// Names of the fields referenced by a template, in order of first reference.
+pub fn field_names(template: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for part in parse(template) {
+        if let Part::Field(name) = part {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Expand a template, replacing field references with the value returned by `lookup`.
+///
+/// References to fields that `lookup` returns `None` for are replaced with an empty string.
+pub fn expand<'a, F>(template: &str, lookup: F) -> String
+    where F: Fn(&str) -> Option<&'a str>
+{
+    let mut expansion = String::new();
+    for part in parse(template) {
+        match part {
+            Part::Literal(s) => expansion.push_str(s),
+            Part::Field(name) => expansion.push_str(lookup(name).unwrap_or("")),
+        }
+    }
+    expansion
+}
@@ -62,12 +62,44 @@ pub trait TransformResultHelper
 
 impl TransformResultHelper for TransformResult {}
 
+/// The fields of a CSV record as read from the input file, accessible by name.
+pub struct InputRecord<'a>
+{
+    headers: &'a [String],
+    field_values: &'a [String],
+}
+
+impl<'a> InputRecord<'a>
+{
+    pub fn new(headers: &'a [String], field_values: &'a [String]) -> InputRecord<'a> {
+        InputRecord {
+            headers: headers,
+            field_values: field_values,
+        }
+    }
+
+    /// Value of the field with the specified name, or `None` if the field is missing.
+    pub fn get(&self, field_name: &str) -> Option<&'a str> {
+        self.headers.iter().position(|h| h == field_name)
+            .and_then(|field_n| self.field_values.get(field_n))
+            .map(|v| v.as_str())
+    }
+}
+
 pub trait Transformer
 {
+    /// Transform a field with access to the other fields of its record, for transformers that
+    /// depend on other fields.
+    ///
+    /// Defaults to `transform`, ignoring the record.
+    fn transform_in_record(&self, field_value: &str, field_name: &str, _record: &InputRecord, record_n: usize) -> TransformResult {
+        self.transform(field_value, field_name, record_n)
+    }
+
     fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult;
 }
 
-#[derive(RustcEncodable, Deserialize, Serialize, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct TransformError
 {
     pub record_n: usize,
@@ -89,3 +121,16 @@ impl error::Error for TransformError
         &self.reason
     }
 }
+
+// Encoded by hand since current compilers no longer support `#[derive(RustcEncodable)]`.
+impl ::rustc_serialize::Encodable for TransformError
+{
+    fn encode<S: ::rustc_serialize::Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        s.emit_struct("TransformError", 4, |s| {
+            s.emit_struct_field("record_n", 0, |s| self.record_n.encode(s))?;
+            s.emit_struct_field("field_name", 1, |s| self.field_name.encode(s))?;
+            s.emit_struct_field("field_value", 2, |s| self.field_value.encode(s))?;
+            s.emit_struct_field("reason", 3, |s| self.reason.encode(s))
+        })
+    }
+}
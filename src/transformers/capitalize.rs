@@ -4,8 +4,29 @@ use transformer::{
     TransformResult
 };
 
+use unicode_normalization::char::is_combining_mark;
 use unicode_segmentation::UnicodeSegmentation;
 
+/// Particles of surnames that are lowercase unless they begin the name, e.g. "van" in "Ludwig
+/// van Beethoven".
+static PARTICLES: &'static [&'static str] = &[
+    "da", "das", "de", "del", "della", "der", "den", "di", "do", "dos", "du", "la", "le", "ten",
+    "ter", "van", "von",
+];
+
+/// Name suffixes in their conventional spelling, recognized when they don't begin the name.
+static SUFFIXES: &'static [&'static str] = &[
+    "II", "III", "IV", "VI", "VII", "VIII", "Jr", "Sr", "PhD", "MD", "DDS", "DVM", "Esq", "CPA",
+    "RN",
+];
+
+/// Surnames starting with "Mac" that aren't capitalized like "MacDonald".
+static MAC_EXCEPTIONS: &'static [&'static str] = &[
+    "macario", "macedo", "macek", "macevicius", "machado", "machar", "machen", "machin",
+    "machlin", "machon", "machuca", "macias", "macin", "maciulis", "mackay", "macken", "mackey",
+    "mackie", "mackintosh", "macklin", "mackmin", "macomber", "macon", "macquarie", "macri",
+];
+
 pub fn capitalize(string: &str) -> String
 {
     string.unicode_words()
@@ -20,14 +41,131 @@ fn capitalize_word(word: &str) -> String
         .collect()
 }
 
+/// Capitalize a person's name, preserving its punctuation and whitespace.
+///
+/// Handles "Mc", "Mac" and "O'" prefixes, lowercase particles like "van" and "de", and suffixes
+/// like "III" and "PhD". Words matching one of the exceptions, regardless of case, are spelled as
+/// the exception.
+///
+/// ```
+/// use csv_sanity::transformers::capitalize_name;
+///
+/// assert_eq!(capitalize_name("O'BRIEN-MCDONALD", &[]), "O'Brien-McDonald");
+/// assert_eq!(capitalize_name("ludwig  VAN beethoven iii", &[]), "Ludwig  van Beethoven III");
+/// assert_eq!(capitalize_name("ANNA MACHADO, PHD", &[]), "Anna Machado, PhD");
+/// assert_eq!(capitalize_name("DANNY DEVITO", &["DeVito".to_string()]), "Danny DeVito");
+/// assert_eq!(capitalize_name("RENE\u{301}E", &[]), "Rene\u{301}e");
+/// ```
+pub fn capitalize_name(name: &str, exceptions: &[String]) -> String
+{
+    // Split the name into runs of alphanumeric characters and runs of everything else, keeping
+    // combining marks, as in decomposed "é", with the letters they modify.
+    let mut tokens: Vec<(bool, String)> = Vec::new();
+    for c in name.chars() {
+        let is_word = c.is_alphanumeric() || is_combining_mark(c);
+        match tokens.last_mut() {
+            Some(&mut (last_is_word, ref mut token)) if last_is_word == is_word => token.push(c),
+            _ => tokens.push((is_word, c.to_string())),
+        }
+    }
+
+    let mut capitalized = String::with_capacity(name.len());
+    let mut word_n = 0;
+    for (i, &(is_word, ref token)) in tokens.iter().enumerate() {
+        if !is_word {
+            capitalized.push_str(token);
+            continue;
+        }
+        let previous = |n: usize| if i >= n { Some(tokens[i - n].1.as_str()) } else { None };
+        let after_apostrophe = previous(1).map(|s| s == "'" || s == "’").unwrap_or(false);
+        let is_last_word = !tokens[i + 1..].iter().any(|&(is_word, _)| is_word);
+        capitalized.push_str(&capitalize_name_word(token, word_n, is_last_word, after_apostrophe, previous(2), exceptions));
+        word_n += 1;
+    }
+    capitalized
+}
+
+fn capitalize_name_word(word: &str, word_n: usize, is_last_word: bool, after_apostrophe: bool,
+                        before_apostrophe: Option<&str>, exceptions: &[String]) -> String
+{
+    let lowercase = word.to_lowercase();
+    if let Some(exception) = exceptions.iter().find(|e| e.to_lowercase() == lowercase) {
+        return exception.clone();
+    }
+    if after_apostrophe {
+        // Capitalize after prefixes like "O'" and "D'", but not possessives like "John's".
+        return match before_apostrophe {
+            Some(prefix) if prefix.chars().count() == 1 => capitalize_word(word),
+            _ => lowercase,
+        };
+    }
+    if word_n > 0 {
+        if let Some(suffix) = SUFFIXES.iter().find(|s| s.to_lowercase() == lowercase) {
+            return suffix.to_string();
+        }
+        if !is_last_word && PARTICLES.contains(&lowercase.as_str()) {
+            return lowercase;
+        }
+    }
+    if lowercase.starts_with("mc") && lowercase.chars().count() > 2 {
+        return format!("Mc{}", capitalize_word(&lowercase[2..]));
+    }
+    if lowercase.starts_with("mac") && lowercase.chars().count() > 5 && !MAC_EXCEPTIONS.contains(&lowercase.as_str()) {
+        return format!("Mac{}", capitalize_word(&lowercase[3..]));
+    }
+    capitalize_word(word)
+}
+
+/// How to capitalize fields.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum CapitalizeMode {
+    /// Capitalize each word, joining the words with single spaces and dropping punctuation.
+    Words,
+    /// Capitalize people's names, preserving punctuation and whitespace. See `capitalize_name`.
+    Names,
+}
+
+impl Default for CapitalizeMode {
+    fn default() -> CapitalizeMode {
+        CapitalizeMode::Words
+    }
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
-pub struct CapitalizeTransformer {}
+pub struct CapitalizeTransformer {
+    #[serde(default)]
+    mode: CapitalizeMode,
+    /// Words spelled exactly as given regardless of case, e.g. "DeVito".
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    exceptions: Vec<String>,
+}
 
 impl CapitalizeTransformer
 {
     pub fn new() -> CapitalizeTransformer
     {
-        CapitalizeTransformer {}
+        CapitalizeTransformer {
+            mode: CapitalizeMode::Words,
+            exceptions: Vec::new(),
+        }
+    }
+
+    /// Capitalize people's names, preserving punctuation and whitespace. See `capitalize_name`.
+    pub fn for_names() -> CapitalizeTransformer
+    {
+        CapitalizeTransformer {
+            mode: CapitalizeMode::Names,
+            .. Self::new()
+        }
+    }
+
+    /// Spell words matching one of the exceptions, regardless of case, as the exception.
+    pub fn with_exceptions(self, exceptions: Vec<String>) -> CapitalizeTransformer
+    {
+        CapitalizeTransformer {
+            exceptions: exceptions,
+            .. self
+        }
     }
 }
 
@@ -35,7 +173,22 @@ impl Transformer for CapitalizeTransformer
 {
     fn transform(&self, field_value: &str, _: &str, _: usize) -> TransformResult
     {
-        let result = capitalize(field_value);
+        let result = match self.mode {
+            CapitalizeMode::Words if self.exceptions.is_empty() => capitalize(field_value),
+            CapitalizeMode::Words => {
+                field_value.unicode_words()
+                    .map(|word| {
+                        let lowercase = word.to_lowercase();
+                        match self.exceptions.iter().find(|e| e.to_lowercase() == lowercase) {
+                            Some(exception) => exception.clone(),
+                            None => capitalize_word(word),
+                        }
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            },
+            CapitalizeMode::Names => capitalize_name(field_value, &self.exceptions),
+        };
         TransformResult::present(&result)
     }
 }
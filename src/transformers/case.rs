@@ -0,0 +1,168 @@
+use Transformer;
+use transformer::{
+    TransformResultHelper,
+    TransformResult
+};
+
+use unicode_normalization::char::is_combining_mark;
+
+/// A case to convert fields to.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Case {
+    /// "hello world"
+    Lower,
+    /// "HELLO WORLD"
+    Upper,
+    /// "Hello world. Goodbye world."
+    Sentence,
+    /// "hello_world"
+    Snake,
+    /// "hello-world"
+    Kebab,
+    /// "helloWorld"
+    Camel,
+}
+
+/// Split an identifier into words at non-alphanumeric characters and at case changes, e.g.
+/// "parseHTTPResponse_v2" into "parse", "HTTP", "Response" and "v2". Combining marks are kept
+/// with the letters they modify.
+fn identifier_words(value: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let chars: Vec<char> = value.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() && !is_combining_mark(c) {
+            if !word.is_empty() {
+                words.push(word);
+                word = String::new();
+            }
+            continue;
+        }
+        if let Some(previous) = word.chars().rev().find(|&p| !is_combining_mark(p)) {
+            let next_is_lowercase = chars.get(i + 1).map(|n| n.is_lowercase()).unwrap_or(false);
+            // Start a new word at "aB" and at the last capital of an acronym followed by a word,
+            // as in "HTTPResponse".
+            let is_boundary = c.is_uppercase() && (previous.is_lowercase() || previous.is_numeric()
+                || (previous.is_uppercase() && next_is_lowercase));
+            if is_boundary {
+                words.push(word);
+                word = String::new();
+            }
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Uppercase the first character of a string and lowercase the rest.
+fn capitalize_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.as_str().to_lowercase().chars()).collect(),
+        None => String::new(),
+    }
+}
+
+/// Lowercase a string, uppercasing the first letter of each sentence.
+fn sentence_case(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut sentence_start = true;
+    for c in value.to_lowercase().chars() {
+        if sentence_start && c.is_alphabetic() {
+            result.extend(c.to_uppercase());
+            sentence_start = false;
+        } else {
+            result.push(c);
+            if c == '.' || c == '!' || c == '?' {
+                sentence_start = true;
+            }
+        }
+    }
+    result
+}
+
+/// Convert a string to the specified case, using Unicode's locale-independent case mappings,
+/// e.g. "ß" uppercases to "SS".
+///
+/// ```
+/// use csv_sanity::transformers::{
+///     convert_case,
+///     Case,
+/// };
+///
+/// assert_eq!(convert_case("Straße", Case::Upper), "STRASSE");
+/// assert_eq!(convert_case("parseHTTPResponse v2", Case::Snake), "parse_http_response_v2");
+/// assert_eq!(convert_case("first-name", Case::Camel), "firstName");
+/// assert_eq!(convert_case("Cafe\u{301}Owner", Case::Snake), "cafe\u{301}_owner");
+/// assert_eq!(convert_case("HELLO WORLD. GOODBYE!", Case::Sentence), "Hello world. Goodbye!");
+/// ```
+pub fn convert_case(value: &str, case: Case) -> String {
+    match case {
+        Case::Lower => value.to_lowercase(),
+        Case::Upper => value.to_uppercase(),
+        Case::Sentence => sentence_case(value),
+        Case::Snake => identifier_words(value).iter().map(|w| w.to_lowercase()).collect::<Vec<String>>().join("_"),
+        Case::Kebab => identifier_words(value).iter().map(|w| w.to_lowercase()).collect::<Vec<String>>().join("-"),
+        Case::Camel => {
+            identifier_words(value).iter().enumerate()
+                .map(|(i, w)| if i == 0 { w.to_lowercase() } else { capitalize_first(w) })
+                .collect()
+        },
+    }
+}
+
+/// Whether a string has no lowercase letters or no uppercase letters.
+fn is_uniform_case(value: &str) -> bool {
+    !value.chars().any(char::is_lowercase) || !value.chars().any(char::is_uppercase)
+}
+
+/// Convert fields to a case. See `convert_case`.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformers::{
+///     CaseTransformer,
+///     Case,
+/// };
+///
+/// let transformer = CaseTransformer::new(Case::Lower).only_if_uniform_case();
+/// assert_eq!(transformer.transform("ACME WIDGETS", "Company", 1), Ok(Some("acme widgets".to_string())));
+/// assert_eq!(transformer.transform("iPhone Repairs", "Company", 1), Ok(Some("iPhone Repairs".to_string())));
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct CaseTransformer {
+    case: Case,
+    /// Leave fields with both uppercase and lowercase letters unchanged.
+    #[serde(default)]
+    only_if_uniform_case: bool,
+}
+
+impl CaseTransformer {
+    pub fn new(case: Case) -> CaseTransformer {
+        CaseTransformer {
+            case: case,
+            only_if_uniform_case: false,
+        }
+    }
+
+    /// Only convert fields that are entirely uppercase or entirely lowercase, leaving mixed case
+    /// fields unchanged.
+    pub fn only_if_uniform_case(self) -> CaseTransformer {
+        CaseTransformer {
+            only_if_uniform_case: true,
+            .. self
+        }
+    }
+}
+
+impl Transformer for CaseTransformer {
+    fn transform(&self, field_value: &str, _: &str, _: usize) -> TransformResult {
+        if self.only_if_uniform_case && !is_uniform_case(field_value) {
+            return TransformResult::present(field_value);
+        }
+        TransformResult::present(&convert_case(field_value, self.case))
+    }
+}
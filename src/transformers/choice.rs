@@ -7,6 +7,10 @@ use transformer::{
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
 pub struct ChoiceTransformer {
     choices: Vec<String>,
+    #[serde(default)]
+    case_insensitive: bool,
+    #[serde(default)]
+    max_edit_distance: usize,
 }
 
 impl ChoiceTransformer
@@ -15,8 +19,66 @@ impl ChoiceTransformer
     {
         ChoiceTransformer {
             choices: choices,
+            case_insensitive: false,
+            max_edit_distance: 0,
         }
     }
+
+    /// Match choices regardless of case, normalizing fields to the spelling of the matched choice.
+    pub fn case_insensitive(self) -> ChoiceTransformer
+    {
+        ChoiceTransformer {
+            case_insensitive: true,
+            .. self
+        }
+    }
+
+    /// Accept fields within an edit distance of a single choice, normalizing them to that choice
+    /// and logging a warning.
+    ///
+    /// ```
+    /// use csv_sanity::Transformer;
+    /// use csv_sanity::transformers::ChoiceTransformer;
+    ///
+    /// let choices = vec!["Yes".to_string(), "No".to_string()];
+    /// let transformer = ChoiceTransformer::new(choices).case_insensitive().with_max_edit_distance(1);
+    /// assert_eq!(transformer.transform("yes", "Opt In", 1), Ok(Some("Yes".to_string())));
+    /// assert_eq!(transformer.transform("yse", "Opt In", 1).unwrap_err().reason,
+    ///     "not a valid choice, closest is \"Yes\"");
+    /// assert_eq!(transformer.transform("Yess", "Opt In", 1), Ok(Some("Yes".to_string())));
+    /// ```
+    pub fn with_max_edit_distance(self, max_edit_distance: usize) -> ChoiceTransformer
+    {
+        ChoiceTransformer {
+            max_edit_distance: max_edit_distance,
+            .. self
+        }
+    }
+
+    fn normalize(&self, value: &str) -> String
+    {
+        if self.case_insensitive {
+            value.to_lowercase()
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize
+{
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..b.len() + 1).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + if a_char == *b_char { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
 }
 
 impl Transformer for ChoiceTransformer
@@ -24,14 +86,33 @@ impl Transformer for ChoiceTransformer
     fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult
     {
         if self.choices.contains(&field_value.to_string()) {
-            TransformResult::present(&field_value)
-        } else {
-            TransformResult::error(
+            return TransformResult::present(&field_value);
+        }
+
+        let value = self.normalize(field_value);
+        if let Some(choice) = self.choices.iter().find(|c| self.normalize(c) == value) {
+            return TransformResult::present(choice);
+        }
+
+        let mut distances: Vec<(usize, &String)> = self.choices.iter()
+            .map(|c| (edit_distance(&value, &self.normalize(c)), c))
+            .collect();
+        distances.sort_by_key(|&(distance, _)| distance);
+        match (distances.get(0), distances.get(1)) {
+            (Some(&(distance, choice)), next) if distance <= self.max_edit_distance && next.map(|n| n.0) != Some(distance) => {
+                warn!(
+                    "record {}: field {} value {:?} accepted as choice {:?} with edit distance {}",
+                    record_n, field_name, field_value, choice, distance
+                );
+                TransformResult::present(choice)
+            },
+            (Some(&(_, choice)), _) => TransformResult::error(
                 field_value,
                 field_name,
                 record_n,
-                &format!("not in valid choices {:?}", self.choices)
-            )
+                &format!("not a valid choice, closest is {:?}", choice)
+            ),
+            (None, _) => TransformResult::error(field_value, field_name, record_n, "not a valid choice"),
         }
     }
 }
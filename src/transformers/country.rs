@@ -0,0 +1,373 @@
+use Transformer;
+use transformer::{
+    TransformResultHelper,
+    TransformResult
+};
+
+use std::collections::HashMap;
+
+/// ISO 3166-1 countries: alpha-2 code, alpha-3 code, English short name and other names the
+/// country is commonly known by.
+static COUNTRIES: &'static [(&'static str, &'static str, &'static str, &'static [&'static str])] = &[
+    ("AD", "AND", "Andorra", &[]),
+    ("AE", "ARE", "United Arab Emirates", &["UAE"]),
+    ("AF", "AFG", "Afghanistan", &[]),
+    ("AG", "ATG", "Antigua and Barbuda", &[]),
+    ("AI", "AIA", "Anguilla", &[]),
+    ("AL", "ALB", "Albania", &[]),
+    ("AM", "ARM", "Armenia", &[]),
+    ("AO", "AGO", "Angola", &[]),
+    ("AQ", "ATA", "Antarctica", &[]),
+    ("AR", "ARG", "Argentina", &[]),
+    ("AS", "ASM", "American Samoa", &[]),
+    ("AT", "AUT", "Austria", &[]),
+    ("AU", "AUS", "Australia", &[]),
+    ("AW", "ABW", "Aruba", &[]),
+    ("AX", "ALA", "Åland Islands", &["Aland Islands", "Aland"]),
+    ("AZ", "AZE", "Azerbaijan", &[]),
+    ("BA", "BIH", "Bosnia and Herzegovina", &["Bosnia"]),
+    ("BB", "BRB", "Barbados", &[]),
+    ("BD", "BGD", "Bangladesh", &[]),
+    ("BE", "BEL", "Belgium", &[]),
+    ("BF", "BFA", "Burkina Faso", &[]),
+    ("BG", "BGR", "Bulgaria", &[]),
+    ("BH", "BHR", "Bahrain", &[]),
+    ("BI", "BDI", "Burundi", &[]),
+    ("BJ", "BEN", "Benin", &[]),
+    ("BL", "BLM", "Saint Barthélemy", &["Saint Barthelemy", "St Barts"]),
+    ("BM", "BMU", "Bermuda", &[]),
+    ("BN", "BRN", "Brunei", &["Brunei Darussalam"]),
+    ("BO", "BOL", "Bolivia", &["Bolivia, Plurinational State of"]),
+    ("BQ", "BES", "Caribbean Netherlands", &["Bonaire, Sint Eustatius and Saba", "Bonaire"]),
+    ("BR", "BRA", "Brazil", &["Brasil"]),
+    ("BS", "BHS", "Bahamas", &[]),
+    ("BT", "BTN", "Bhutan", &[]),
+    ("BV", "BVT", "Bouvet Island", &[]),
+    ("BW", "BWA", "Botswana", &[]),
+    ("BY", "BLR", "Belarus", &[]),
+    ("BZ", "BLZ", "Belize", &[]),
+    ("CA", "CAN", "Canada", &[]),
+    ("CC", "CCK", "Cocos (Keeling) Islands", &["Cocos Islands", "Keeling Islands"]),
+    ("CD", "COD", "Democratic Republic of the Congo", &["Congo, Democratic Republic of the", "DR Congo", "DRC", "Congo-Kinshasa"]),
+    ("CF", "CAF", "Central African Republic", &[]),
+    ("CG", "COG", "Republic of the Congo", &["Congo", "Congo-Brazzaville"]),
+    ("CH", "CHE", "Switzerland", &[]),
+    ("CI", "CIV", "Côte d'Ivoire", &["Cote d'Ivoire", "Ivory Coast"]),
+    ("CK", "COK", "Cook Islands", &[]),
+    ("CL", "CHL", "Chile", &[]),
+    ("CM", "CMR", "Cameroon", &[]),
+    ("CN", "CHN", "China", &["People's Republic of China", "PRC"]),
+    ("CO", "COL", "Colombia", &[]),
+    ("CR", "CRI", "Costa Rica", &[]),
+    ("CU", "CUB", "Cuba", &[]),
+    ("CV", "CPV", "Cabo Verde", &["Cape Verde"]),
+    ("CW", "CUW", "Curaçao", &["Curacao"]),
+    ("CX", "CXR", "Christmas Island", &[]),
+    ("CY", "CYP", "Cyprus", &[]),
+    ("CZ", "CZE", "Czechia", &["Czech Republic"]),
+    ("DE", "DEU", "Germany", &["Deutschland"]),
+    ("DJ", "DJI", "Djibouti", &[]),
+    ("DK", "DNK", "Denmark", &[]),
+    ("DM", "DMA", "Dominica", &[]),
+    ("DO", "DOM", "Dominican Republic", &[]),
+    ("DZ", "DZA", "Algeria", &[]),
+    ("EC", "ECU", "Ecuador", &[]),
+    ("EE", "EST", "Estonia", &[]),
+    ("EG", "EGY", "Egypt", &[]),
+    ("EH", "ESH", "Western Sahara", &[]),
+    ("ER", "ERI", "Eritrea", &[]),
+    ("ES", "ESP", "Spain", &["España", "Espana"]),
+    ("ET", "ETH", "Ethiopia", &[]),
+    ("FI", "FIN", "Finland", &[]),
+    ("FJ", "FJI", "Fiji", &[]),
+    ("FK", "FLK", "Falkland Islands", &["Falkland Islands (Malvinas)", "Falklands"]),
+    ("FM", "FSM", "Micronesia", &["Micronesia, Federated States of", "Federated States of Micronesia"]),
+    ("FO", "FRO", "Faroe Islands", &[]),
+    ("FR", "FRA", "France", &[]),
+    ("GA", "GAB", "Gabon", &[]),
+    ("GB", "GBR", "United Kingdom", &["UK", "Great Britain", "Britain", "United Kingdom of Great Britain and Northern Ireland", "England", "Scotland", "Wales", "Northern Ireland"]),
+    ("GD", "GRD", "Grenada", &[]),
+    ("GE", "GEO", "Georgia", &[]),
+    ("GF", "GUF", "French Guiana", &[]),
+    ("GG", "GGY", "Guernsey", &[]),
+    ("GH", "GHA", "Ghana", &[]),
+    ("GI", "GIB", "Gibraltar", &[]),
+    ("GL", "GRL", "Greenland", &[]),
+    ("GM", "GMB", "Gambia", &[]),
+    ("GN", "GIN", "Guinea", &[]),
+    ("GP", "GLP", "Guadeloupe", &[]),
+    ("GQ", "GNQ", "Equatorial Guinea", &[]),
+    ("GR", "GRC", "Greece", &[]),
+    ("GS", "SGS", "South Georgia and the South Sandwich Islands", &[]),
+    ("GT", "GTM", "Guatemala", &[]),
+    ("GU", "GUM", "Guam", &[]),
+    ("GW", "GNB", "Guinea-Bissau", &[]),
+    ("GY", "GUY", "Guyana", &[]),
+    ("HK", "HKG", "Hong Kong", &[]),
+    ("HM", "HMD", "Heard Island and McDonald Islands", &[]),
+    ("HN", "HND", "Honduras", &[]),
+    ("HR", "HRV", "Croatia", &[]),
+    ("HT", "HTI", "Haiti", &[]),
+    ("HU", "HUN", "Hungary", &[]),
+    ("ID", "IDN", "Indonesia", &[]),
+    ("IE", "IRL", "Ireland", &["Republic of Ireland", "Eire"]),
+    ("IL", "ISR", "Israel", &[]),
+    ("IM", "IMN", "Isle of Man", &[]),
+    ("IN", "IND", "India", &[]),
+    ("IO", "IOT", "British Indian Ocean Territory", &[]),
+    ("IQ", "IRQ", "Iraq", &[]),
+    ("IR", "IRN", "Iran", &["Iran, Islamic Republic of", "Islamic Republic of Iran"]),
+    ("IS", "ISL", "Iceland", &[]),
+    ("IT", "ITA", "Italy", &["Italia"]),
+    ("JE", "JEY", "Jersey", &[]),
+    ("JM", "JAM", "Jamaica", &[]),
+    ("JO", "JOR", "Jordan", &[]),
+    ("JP", "JPN", "Japan", &[]),
+    ("KE", "KEN", "Kenya", &[]),
+    ("KG", "KGZ", "Kyrgyzstan", &[]),
+    ("KH", "KHM", "Cambodia", &[]),
+    ("KI", "KIR", "Kiribati", &[]),
+    ("KM", "COM", "Comoros", &[]),
+    ("KN", "KNA", "Saint Kitts and Nevis", &[]),
+    ("KP", "PRK", "North Korea", &["Korea, Democratic People's Republic of", "Democratic People's Republic of Korea", "DPRK"]),
+    ("KR", "KOR", "South Korea", &["Korea, Republic of", "Republic of Korea", "Korea"]),
+    ("KW", "KWT", "Kuwait", &[]),
+    ("KY", "CYM", "Cayman Islands", &[]),
+    ("KZ", "KAZ", "Kazakhstan", &[]),
+    ("LA", "LAO", "Laos", &["Lao People's Democratic Republic", "Lao PDR"]),
+    ("LB", "LBN", "Lebanon", &[]),
+    ("LC", "LCA", "Saint Lucia", &[]),
+    ("LI", "LIE", "Liechtenstein", &[]),
+    ("LK", "LKA", "Sri Lanka", &[]),
+    ("LR", "LBR", "Liberia", &[]),
+    ("LS", "LSO", "Lesotho", &[]),
+    ("LT", "LTU", "Lithuania", &[]),
+    ("LU", "LUX", "Luxembourg", &[]),
+    ("LV", "LVA", "Latvia", &[]),
+    ("LY", "LBY", "Libya", &[]),
+    ("MA", "MAR", "Morocco", &[]),
+    ("MC", "MCO", "Monaco", &[]),
+    ("MD", "MDA", "Moldova", &["Moldova, Republic of", "Republic of Moldova"]),
+    ("ME", "MNE", "Montenegro", &[]),
+    ("MF", "MAF", "Saint Martin", &["Saint Martin (French part)"]),
+    ("MG", "MDG", "Madagascar", &[]),
+    ("MH", "MHL", "Marshall Islands", &[]),
+    ("MK", "MKD", "North Macedonia", &["Macedonia", "Republic of North Macedonia"]),
+    ("ML", "MLI", "Mali", &[]),
+    ("MM", "MMR", "Myanmar", &["Burma"]),
+    ("MN", "MNG", "Mongolia", &[]),
+    ("MO", "MAC", "Macao", &["Macau"]),
+    ("MP", "MNP", "Northern Mariana Islands", &[]),
+    ("MQ", "MTQ", "Martinique", &[]),
+    ("MR", "MRT", "Mauritania", &[]),
+    ("MS", "MSR", "Montserrat", &[]),
+    ("MT", "MLT", "Malta", &[]),
+    ("MU", "MUS", "Mauritius", &[]),
+    ("MV", "MDV", "Maldives", &[]),
+    ("MW", "MWI", "Malawi", &[]),
+    ("MX", "MEX", "Mexico", &["México"]),
+    ("MY", "MYS", "Malaysia", &[]),
+    ("MZ", "MOZ", "Mozambique", &[]),
+    ("NA", "NAM", "Namibia", &[]),
+    ("NC", "NCL", "New Caledonia", &[]),
+    ("NE", "NER", "Niger", &[]),
+    ("NF", "NFK", "Norfolk Island", &[]),
+    ("NG", "NGA", "Nigeria", &[]),
+    ("NI", "NIC", "Nicaragua", &[]),
+    ("NL", "NLD", "Netherlands", &["Holland", "Netherlands, Kingdom of the"]),
+    ("NO", "NOR", "Norway", &[]),
+    ("NP", "NPL", "Nepal", &[]),
+    ("NR", "NRU", "Nauru", &[]),
+    ("NU", "NIU", "Niue", &[]),
+    ("NZ", "NZL", "New Zealand", &[]),
+    ("OM", "OMN", "Oman", &[]),
+    ("PA", "PAN", "Panama", &[]),
+    ("PE", "PER", "Peru", &[]),
+    ("PF", "PYF", "French Polynesia", &[]),
+    ("PG", "PNG", "Papua New Guinea", &[]),
+    ("PH", "PHL", "Philippines", &[]),
+    ("PK", "PAK", "Pakistan", &[]),
+    ("PL", "POL", "Poland", &[]),
+    ("PM", "SPM", "Saint Pierre and Miquelon", &[]),
+    ("PN", "PCN", "Pitcairn", &["Pitcairn Islands"]),
+    ("PR", "PRI", "Puerto Rico", &[]),
+    ("PS", "PSE", "Palestine", &["Palestine, State of", "State of Palestine"]),
+    ("PT", "PRT", "Portugal", &[]),
+    ("PW", "PLW", "Palau", &[]),
+    ("PY", "PRY", "Paraguay", &[]),
+    ("QA", "QAT", "Qatar", &[]),
+    ("RE", "REU", "Réunion", &["Reunion"]),
+    ("RO", "ROU", "Romania", &[]),
+    ("RS", "SRB", "Serbia", &[]),
+    ("RU", "RUS", "Russia", &["Russian Federation"]),
+    ("RW", "RWA", "Rwanda", &[]),
+    ("SA", "SAU", "Saudi Arabia", &[]),
+    ("SB", "SLB", "Solomon Islands", &[]),
+    ("SC", "SYC", "Seychelles", &[]),
+    ("SD", "SDN", "Sudan", &[]),
+    ("SE", "SWE", "Sweden", &[]),
+    ("SG", "SGP", "Singapore", &[]),
+    ("SH", "SHN", "Saint Helena", &["Saint Helena, Ascension and Tristan da Cunha"]),
+    ("SI", "SVN", "Slovenia", &[]),
+    ("SJ", "SJM", "Svalbard and Jan Mayen", &[]),
+    ("SK", "SVK", "Slovakia", &["Slovak Republic"]),
+    ("SL", "SLE", "Sierra Leone", &[]),
+    ("SM", "SMR", "San Marino", &[]),
+    ("SN", "SEN", "Senegal", &[]),
+    ("SO", "SOM", "Somalia", &[]),
+    ("SR", "SUR", "Suriname", &[]),
+    ("SS", "SSD", "South Sudan", &[]),
+    ("ST", "STP", "São Tomé and Príncipe", &["Sao Tome and Principe"]),
+    ("SV", "SLV", "El Salvador", &[]),
+    ("SX", "SXM", "Sint Maarten", &["Sint Maarten (Dutch part)"]),
+    ("SY", "SYR", "Syria", &["Syrian Arab Republic"]),
+    ("SZ", "SWZ", "Eswatini", &["Swaziland"]),
+    ("TC", "TCA", "Turks and Caicos Islands", &[]),
+    ("TD", "TCD", "Chad", &[]),
+    ("TF", "ATF", "French Southern Territories", &[]),
+    ("TG", "TGO", "Togo", &[]),
+    ("TH", "THA", "Thailand", &[]),
+    ("TJ", "TJK", "Tajikistan", &[]),
+    ("TK", "TKL", "Tokelau", &[]),
+    ("TL", "TLS", "Timor-Leste", &["East Timor"]),
+    ("TM", "TKM", "Turkmenistan", &[]),
+    ("TN", "TUN", "Tunisia", &[]),
+    ("TO", "TON", "Tonga", &[]),
+    ("TR", "TUR", "Türkiye", &["Turkey", "Turkiye"]),
+    ("TT", "TTO", "Trinidad and Tobago", &[]),
+    ("TV", "TUV", "Tuvalu", &[]),
+    ("TW", "TWN", "Taiwan", &["Taiwan, Province of China"]),
+    ("TZ", "TZA", "Tanzania", &["Tanzania, United Republic of", "United Republic of Tanzania"]),
+    ("UA", "UKR", "Ukraine", &[]),
+    ("UG", "UGA", "Uganda", &[]),
+    ("UM", "UMI", "United States Minor Outlying Islands", &[]),
+    ("US", "USA", "United States", &["United States of America", "America"]),
+    ("UY", "URY", "Uruguay", &[]),
+    ("UZ", "UZB", "Uzbekistan", &[]),
+    ("VA", "VAT", "Vatican City", &["Holy See", "Vatican"]),
+    ("VC", "VCT", "Saint Vincent and the Grenadines", &[]),
+    ("VE", "VEN", "Venezuela", &["Venezuela, Bolivarian Republic of"]),
+    ("VG", "VGB", "British Virgin Islands", &["Virgin Islands, British"]),
+    ("VI", "VIR", "U.S. Virgin Islands", &["Virgin Islands, U.S."]),
+    ("VN", "VNM", "Vietnam", &["Viet Nam"]),
+    ("VU", "VUT", "Vanuatu", &[]),
+    ("WF", "WLF", "Wallis and Futuna", &[]),
+    ("WS", "WSM", "Samoa", &[]),
+    ("YE", "YEM", "Yemen", &[]),
+    ("YT", "MYT", "Mayotte", &[]),
+    ("ZA", "ZAF", "South Africa", &[]),
+    ("ZM", "ZMB", "Zambia", &[]),
+    ("ZW", "ZWE", "Zimbabwe", &[]),
+];
+
+lazy_static! {
+    /// Index into `COUNTRIES` by the name key of each country's codes, name and other names.
+    static ref COUNTRY_INDEX: HashMap<String, usize> = {
+        let mut index = HashMap::new();
+        for (i, &(alpha2, alpha3, name, other_names)) in COUNTRIES.iter().enumerate() {
+            for n in [alpha2, alpha3, name].iter().chain(other_names.iter()) {
+                index.insert(name_key(n), i);
+            }
+        }
+        index
+    };
+}
+
+/// Normalize a place name for lookup: lowercased, ignoring punctuation, a leading "the" and
+/// "St" abbreviating "Saint", with hyphens and runs of whitespace as single spaces.
+pub fn name_key(name: &str) -> String {
+    let name: String = name.to_lowercase().chars()
+        .map(|c| if c == '-' { ' ' } else { c })
+        .filter(|&c| !".,'’()".contains(c))
+        .collect();
+    let name = name.replace('&', " and ");
+    let mut words: Vec<&str> = name.split_whitespace()
+        .map(|w| if w == "st" { "saint" } else { w })
+        .collect();
+    if words.len() > 1 && words[0] == "the" {
+        words.remove(0);
+    }
+    words.join(" ")
+}
+
+/// Look up the ISO 3166-1 alpha-2 code of a country by code, name or another common name.
+///
+/// ```
+/// use csv_sanity::transformers::country_code;
+///
+/// assert_eq!(country_code("United Kingdom of Great Britain and Northern Ireland"), Some("GB"));
+/// assert_eq!(country_code("deu"), Some("DE"));
+/// assert_eq!(country_code("Narnia"), None);
+/// ```
+pub fn country_code(country: &str) -> Option<&'static str> {
+    COUNTRY_INDEX.get(&name_key(country)).map(|&i| COUNTRIES[i].0)
+}
+
+/// How to output countries.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum CountryOutput {
+    /// ISO 3166-1 alpha-2 code, e.g. "GB".
+    Alpha2,
+    /// ISO 3166-1 alpha-3 code, e.g. "GBR".
+    Alpha3,
+    /// English short name, e.g. "United Kingdom".
+    Name,
+}
+
+impl Default for CountryOutput {
+    fn default() -> CountryOutput {
+        CountryOutput::Alpha2
+    }
+}
+
+/// Normalize countries given by ISO 3166-1 code, English name or another common name, e.g.
+/// "Great Britain", to their ISO 3166-1 code or name, rejecting unknown countries.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformers::{
+///     CountryTransformer,
+///     CountryOutput,
+/// };
+///
+/// let transformer = CountryTransformer::new().with_output(CountryOutput::Alpha3);
+/// assert_eq!(transformer.transform("the netherlands", "Country", 1), Ok(Some("NLD".to_string())));
+/// assert_eq!(transformer.transform("St. Lucia", "Country", 1), Ok(Some("LCA".to_string())));
+/// assert_eq!(transformer.transform("Narnia", "Country", 1).unwrap_err().reason, "not a valid country");
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct CountryTransformer {
+    #[serde(default)]
+    output: CountryOutput,
+}
+
+impl CountryTransformer {
+    pub fn new() -> CountryTransformer {
+        CountryTransformer {
+            output: Default::default(),
+        }
+    }
+
+    pub fn with_output(self, output: CountryOutput) -> CountryTransformer {
+        CountryTransformer {
+            output: output,
+            .. self
+        }
+    }
+}
+
+impl Transformer for CountryTransformer {
+    fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
+        match COUNTRY_INDEX.get(&name_key(field_value)) {
+            Some(&i) => {
+                let (alpha2, alpha3, name, _) = COUNTRIES[i];
+                TransformResult::present(match self.output {
+                    CountryOutput::Alpha2 => alpha2,
+                    CountryOutput::Alpha3 => alpha3,
+                    CountryOutput::Name => name,
+                })
+            },
+            None => TransformResult::error(field_value, field_name, record_n, "not a valid country"),
+        }
+    }
+}
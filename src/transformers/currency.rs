@@ -0,0 +1,316 @@
+use Transformer;
+use transformer::{
+    TransformResultHelper,
+    TransformResult
+};
+use super::number::parse_decimal;
+
+use std::collections::BTreeMap;
+
+/// ISO 4217 currency codes and the number of minor units (decimal places) of each currency.
+static CURRENCIES: &'static [(&'static str, usize)] = &[
+    ("AED", 2), ("AFN", 2), ("ALL", 2), ("AMD", 2), ("ANG", 2), ("AOA", 2), ("ARS", 2), ("AUD", 2),
+    ("AWG", 2), ("AZN", 2), ("BAM", 2), ("BBD", 2), ("BDT", 2), ("BGN", 2), ("BHD", 3), ("BIF", 0),
+    ("BMD", 2), ("BND", 2), ("BOB", 2), ("BRL", 2), ("BSD", 2), ("BTN", 2), ("BWP", 2), ("BYN", 2),
+    ("BZD", 2), ("CAD", 2), ("CDF", 2), ("CHF", 2), ("CLP", 0), ("CNY", 2), ("COP", 2), ("CRC", 2),
+    ("CUP", 2), ("CVE", 2), ("CZK", 2), ("DJF", 0), ("DKK", 2), ("DOP", 2), ("DZD", 2), ("EGP", 2),
+    ("ERN", 2), ("ETB", 2), ("EUR", 2), ("FJD", 2), ("FKP", 2), ("GBP", 2), ("GEL", 2), ("GHS", 2),
+    ("GIP", 2), ("GMD", 2), ("GNF", 0), ("GTQ", 2), ("GYD", 2), ("HKD", 2), ("HNL", 2), ("HTG", 2),
+    ("HUF", 2), ("IDR", 2), ("ILS", 2), ("INR", 2), ("IQD", 3), ("IRR", 2), ("ISK", 0), ("JMD", 2),
+    ("JOD", 3), ("JPY", 0), ("KES", 2), ("KGS", 2), ("KHR", 2), ("KMF", 0), ("KPW", 2), ("KRW", 0),
+    ("KWD", 3), ("KYD", 2), ("KZT", 2), ("LAK", 2), ("LBP", 2), ("LKR", 2), ("LRD", 2), ("LSL", 2),
+    ("LYD", 3), ("MAD", 2), ("MDL", 2), ("MGA", 2), ("MKD", 2), ("MMK", 2), ("MNT", 2), ("MOP", 2),
+    ("MRU", 2), ("MUR", 2), ("MVR", 2), ("MWK", 2), ("MXN", 2), ("MYR", 2), ("MZN", 2), ("NAD", 2),
+    ("NGN", 2), ("NIO", 2), ("NOK", 2), ("NPR", 2), ("NZD", 2), ("OMR", 3), ("PAB", 2), ("PEN", 2),
+    ("PGK", 2), ("PHP", 2), ("PKR", 2), ("PLN", 2), ("PYG", 0), ("QAR", 2), ("RON", 2), ("RSD", 2),
+    ("RUB", 2), ("RWF", 0), ("SAR", 2), ("SBD", 2), ("SCR", 2), ("SDG", 2), ("SEK", 2), ("SGD", 2),
+    ("SHP", 2), ("SLE", 2), ("SOS", 2), ("SRD", 2), ("SSP", 2), ("STN", 2), ("SVC", 2), ("SYP", 2),
+    ("SZL", 2), ("THB", 2), ("TJS", 2), ("TMT", 2), ("TND", 3), ("TOP", 2), ("TRY", 2), ("TTD", 2),
+    ("TWD", 2), ("TZS", 2), ("UAH", 2), ("UGX", 0), ("USD", 2), ("UYU", 2), ("UZS", 2), ("VES", 2),
+    ("VND", 0), ("VUV", 0), ("WST", 2), ("XAF", 0), ("XCD", 2), ("XOF", 0), ("XPF", 0), ("YER", 2),
+    ("ZAR", 2), ("ZMW", 2), ("ZWL", 2),
+];
+
+/// Currency symbols and the ISO 4217 codes they stand for. Ambiguous symbols stand for the most
+/// common currency using them, e.g. "$" for USD; override them with `CurrencyFormat::with_symbol`.
+static SYMBOLS: &'static [(&'static str, &'static str)] = &[
+    ("US$", "USD"), ("CA$", "CAD"), ("C$", "CAD"), ("AU$", "AUD"), ("A$", "AUD"), ("NZ$", "NZD"),
+    ("HK$", "HKD"), ("S$", "SGD"), ("R$", "BRL"), ("$", "USD"), ("€", "EUR"), ("£", "GBP"),
+    ("¥", "JPY"), ("₹", "INR"), ("₩", "KRW"), ("₽", "RUB"), ("₺", "TRY"), ("₪", "ILS"),
+    ("₫", "VND"), ("₱", "PHP"), ("฿", "THB"), ("zł", "PLN"),
+];
+
+/// Number of minor units of an ISO 4217 currency, if it's a known currency.
+fn minor_units_of(code: &str) -> Option<usize> {
+    CURRENCIES.iter().find(|&&(c, _)| c == code).map(|&(_, units)| units)
+}
+
+/// Guess the decimal and thousands separators of an amount formatted with "." and ",".
+///
+/// If both are present the rightmost is the decimal separator. A lone separator is taken as a
+/// thousands separator if it occurs more than once or is followed by exactly three digits, so
+/// "1,234" and "1.234" are both read as 1234, unless the currency has three minor units.
+fn infer_separators(amount: &str, minor_units: usize) -> (char, Option<char>) {
+    match (amount.rfind('.'), amount.rfind(',')) {
+        (Some(dot), Some(comma)) => if dot > comma { ('.', Some(',')) } else { (',', Some('.')) },
+        (Some(i), None) | (None, Some(i)) => {
+            let separator = if amount[i..].starts_with('.') { '.' } else { ',' };
+            let other = if separator == '.' { ',' } else { '.' };
+            if amount.matches(separator).count() > 1 || (amount.len() - i - 1 == 3 && minor_units != 3) {
+                (other, Some(separator))
+            } else {
+                (separator, Some(other))
+            }
+        },
+        (None, None) => ('.', None),
+    }
+}
+
+/// An amount of money, with the amount formatted to the minor units of its currency.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Money {
+    pub amount: String,
+    pub currency: String,
+}
+
+/// How to parse amounts of money, shared by `CurrencyTransformer` and `CurrencySplitter`.
+///
+/// Amounts may have a currency symbol or ISO 4217 code before or after them, e.g. "$1,234.50",
+/// "USD 1234.5" or "1.234,50 €". Unless the separators are configured they are inferred from each
+/// amount.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct CurrencyFormat {
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    decimal_separator: Option<char>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    thousands_separator: Option<char>,
+    #[serde(default)]
+    parentheses_negative: bool,
+    /// Currency of amounts without a currency symbol or code.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    default_currency: Option<String>,
+    /// Additional or overriding currency symbols, mapped to ISO 4217 codes.
+    #[serde(default, skip_serializing_if="BTreeMap::is_empty")]
+    symbols: BTreeMap<String, String>,
+    /// Currencies to accept; all currencies are accepted if empty.
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    currencies: Vec<String>,
+    /// Number of decimal places to format amounts with, instead of the currency's minor units.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    minor_units: Option<usize>,
+}
+
+impl CurrencyFormat {
+    pub fn new() -> CurrencyFormat {
+        CurrencyFormat {
+            decimal_separator: None,
+            thousands_separator: None,
+            parentheses_negative: false,
+            default_currency: None,
+            symbols: BTreeMap::new(),
+            currencies: Vec::new(),
+            minor_units: None,
+        }
+    }
+
+    /// Use fixed separators instead of inferring them from each amount.
+    pub fn with_separators(self, decimal_separator: char, thousands_separator: Option<char>) -> CurrencyFormat {
+        CurrencyFormat {
+            decimal_separator: Some(decimal_separator),
+            thousands_separator: thousands_separator,
+            .. self
+        }
+    }
+
+    /// Interpret amounts in parentheses, e.g. "($100)", as negative.
+    pub fn with_parentheses_negative(self) -> CurrencyFormat {
+        CurrencyFormat {
+            parentheses_negative: true,
+            .. self
+        }
+    }
+
+    /// Assume amounts without a currency symbol or code are in the specified currency.
+    pub fn with_default_currency(self, code: &str) -> CurrencyFormat {
+        CurrencyFormat {
+            default_currency: Some(code.to_string()),
+            .. self
+        }
+    }
+
+    /// Recognize a currency symbol as standing for the specified currency code.
+    pub fn with_symbol(mut self, symbol: &str, code: &str) -> CurrencyFormat {
+        self.symbols.insert(symbol.to_string(), code.to_string());
+        self
+    }
+
+    /// Format every amount with the specified number of decimal places.
+    pub fn with_minor_units(self, minor_units: usize) -> CurrencyFormat {
+        CurrencyFormat {
+            minor_units: Some(minor_units),
+            .. self
+        }
+    }
+
+    /// Find the longest currency symbol or code matching the start (or end) of a value, returning
+    /// its currency code and the rest of the value.
+    fn strip_currency<'a>(&self, value: &'a str, from_end: bool) -> Result<(Option<String>, &'a str), &'static str> {
+        let matches = |s: &str| if from_end { value.ends_with(s) } else { value.starts_with(s) };
+        let mut best: Option<(&str, &str)> = None;
+        let symbols = self.symbols.iter().map(|(s, c)| (s.as_str(), c.as_str()))
+            .chain(SYMBOLS.iter().map(|&(s, c)| (s, c)));
+        for (symbol, code) in symbols {
+            if matches(symbol) && best.map_or(true, |(b, _)| symbol.len() > b.len()) {
+                best = Some((symbol, code));
+            }
+        }
+        if let Some((symbol, code)) = best {
+            let rest = if from_end { &value[..value.len() - symbol.len()] } else { &value[symbol.len()..] };
+            return Ok((Some(code.to_string()), rest.trim()));
+        }
+
+        let letters: Vec<(usize, char)> = if from_end {
+            value.char_indices().rev().take_while(|&(_, c)| c.is_alphabetic()).collect()
+        } else {
+            value.char_indices().take_while(|&(_, c)| c.is_alphabetic()).collect()
+        };
+        match letters.len() {
+            0 => Ok((None, value)),
+            3 if letters.iter().all(|&(_, c)| c.is_ascii_alphabetic()) => {
+                let (code, rest) = if from_end {
+                    let (rest, code) = value.split_at(letters[2].0);
+                    (code, rest)
+                } else {
+                    value.split_at(3)
+                };
+                let code = code.to_ascii_uppercase();
+                if minor_units_of(&code).is_none() && !self.symbols.values().any(|c| *c == code) {
+                    return Err("unknown currency");
+                }
+                Ok((Some(code), rest.trim()))
+            },
+            _ => Err("unknown currency"),
+        }
+    }
+
+    /// Parse an amount of money, formatting the amount to the minor units of its currency.
+    ///
+    /// Amounts with more decimal places than the currency's minor units are rounded, with halves
+    /// rounded away from zero.
+    ///
+    /// ```
+    /// use csv_sanity::transformers::CurrencyFormat;
+    ///
+    /// let format = CurrencyFormat::new().with_default_currency("USD");
+    /// let money = format.parse("1.234,565 €").unwrap();
+    /// assert_eq!((money.amount.as_str(), money.currency.as_str()), ("1234.57", "EUR"));
+    /// let money = format.parse("-12").unwrap();
+    /// assert_eq!((money.amount.as_str(), money.currency.as_str()), ("-12.00", "USD"));
+    /// assert_eq!(format.parse("JPY 1,234").unwrap().amount, "1234");
+    /// ```
+    pub fn parse(&self, field_value: &str) -> Result<Money, String> {
+        let mut value = field_value.trim();
+        let mut negative = false;
+        if self.parentheses_negative && value.starts_with('(') && value.ends_with(')') {
+            negative = true;
+            value = value[1..value.len() - 1].trim();
+        }
+        let (sign, value) = if value.starts_with('-') || value.starts_with('+') {
+            value.split_at(1)
+        } else {
+            ("", value)
+        };
+
+        let (prefix_currency, value) = self.strip_currency(value, false)?;
+        let (suffix_currency, amount) = self.strip_currency(value, true)?;
+        let currency = match (prefix_currency, suffix_currency) {
+            (Some(ref p), Some(ref s)) if p != s => return Err("conflicting currencies".to_string()),
+            (Some(c), _) | (None, Some(c)) => c,
+            (None, None) => match self.default_currency {
+                Some(ref c) => c.clone(),
+                None => return Err("no currency".to_string()),
+            },
+        };
+        if !self.currencies.is_empty() && !self.currencies.contains(&currency) {
+            return Err(format!("currency {} not allowed", currency));
+        }
+
+        let minor_units = self.minor_units
+            .or_else(|| minor_units_of(&currency))
+            .unwrap_or(2);
+        let (decimal_separator, thousands_separator) = match self.decimal_separator {
+            Some(d) => (d, self.thousands_separator),
+            None => infer_separators(amount, minor_units),
+        };
+        let mut amount = parse_decimal(&format!("{}{}", sign, amount), decimal_separator, thousands_separator)?;
+        if negative {
+            if amount.is_negative() {
+                return Err("not a valid number".to_string());
+            }
+            amount = parse_decimal(&format!("-{}", amount), '.', None)?;
+        }
+        Ok(Money {
+            amount: amount.to_fixed(minor_units),
+            currency: currency,
+        })
+    }
+}
+
+/// What a `CurrencyTransformer` outputs for an amount of money.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum CurrencyOutput {
+    /// The amount followed by the currency code, e.g. "1234.50 USD".
+    AmountAndCode,
+    /// Only the amount, e.g. "1234.50".
+    Amount,
+    /// Only the currency code, e.g. "USD".
+    Code,
+}
+
+impl Default for CurrencyOutput {
+    fn default() -> CurrencyOutput {
+        CurrencyOutput::AmountAndCode
+    }
+}
+
+/// Normalize amounts of money, e.g. "$1,234.5" as "1234.50 USD". See `CurrencyFormat`.
+///
+/// Use a `CurrencySplitter` to output the amount and currency as separate fields.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct CurrencyTransformer {
+    #[serde(flatten)]
+    format: CurrencyFormat,
+    #[serde(default)]
+    output: CurrencyOutput,
+}
+
+impl CurrencyTransformer {
+    pub fn new(format: CurrencyFormat) -> CurrencyTransformer {
+        CurrencyTransformer {
+            format: format,
+            output: Default::default(),
+        }
+    }
+
+    /// Set what to output for each amount. See `CurrencyOutput`.
+    pub fn output(self, output: CurrencyOutput) -> CurrencyTransformer {
+        CurrencyTransformer {
+            output: output,
+            .. self
+        }
+    }
+}
+
+impl Transformer for CurrencyTransformer {
+    fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
+        let money = match self.format.parse(field_value) {
+            Ok(m) => m,
+            Err(reason) => return TransformResult::error(field_value, field_name, record_n, &reason),
+        };
+        match self.output {
+            CurrencyOutput::AmountAndCode => TransformResult::present(&format!("{} {}", money.amount, money.currency)),
+            CurrencyOutput::Amount => TransformResult::present(&money.amount),
+            CurrencyOutput::Code => TransformResult::present(&money.currency),
+        }
+    }
+}
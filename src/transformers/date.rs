@@ -4,38 +4,680 @@ use transformer::{
     TransformResult
 };
 
-use time::{
-    strptime
+use chrono::{
+    Datelike,
+    DateTime,
+    Duration,
+    FixedOffset,
+    Local,
+    LocalResult,
+    Months,
+    NaiveDate,
+    NaiveDateTime,
+    NaiveTime,
+    TimeZone,
 };
+use chrono::format::{
+    Item,
+    StrftimeItems,
+};
+use chrono_tz::Tz;
+use serde::{
+    Serialize,
+    Serializer,
+    Deserialize,
+    Deserializer,
+};
+use std::fmt::{
+    self,
+    Display,
+    Formatter,
+    Write,
+};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+lazy_static! {
+    /// The date of this run, which relative `DateBound`s are relative to.
+    static ref RUN_DATE: NaiveDate = Local::now().date_naive();
+}
+
+/// A format for parsing or formatting dates and times.
+///
+/// Serialized as "ISO8601", "RFC3339" or a custom format string using the
+/// [chrono](https://docs.rs/chrono/0.4/chrono/format/strftime/index.html) `strftime` syntax.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum DateFormat {
+    /// ISO 8601, e.g. "2017-04-30", "13:05:00" or "2017-04-30T13:05:00+02:00".
+    Iso8601,
+    /// RFC 3339, which requires datetimes to have a UTC offset.
+    Rfc3339,
+    Custom(String),
+}
+
+impl DateFormat {
+    /// Parse a format string, rejecting custom formats with invalid syntax.
+    pub fn parse(format: &str) -> Result<DateFormat, String> {
+        match format {
+            "ISO8601" => Ok(DateFormat::Iso8601),
+            "RFC3339" => Ok(DateFormat::Rfc3339),
+            _ if StrftimeItems::new(format).any(|item| item == Item::Error) => {
+                Err(format!("invalid date format {:?}", format))
+            },
+            _ => Ok(DateFormat::Custom(format.to_string())),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match *self {
+            DateFormat::Iso8601 => "ISO8601",
+            DateFormat::Rfc3339 => "RFC3339",
+            DateFormat::Custom(ref format) => format,
+        }
+    }
+}
+
+impl Serialize for DateFormat
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+      where S: Serializer {
+        self.as_str().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateFormat
+{
+    fn deserialize<D>(deserializer: D) -> Result<DateFormat, D::Error>
+      where D: Deserializer<'de>
+    {
+        use serde::de::Error;
+        let string: String = Deserialize::deserialize(deserializer)?;
+        DateFormat::parse(&string).map_err(D::Error::custom)
+    }
+}
+
+/// Whether fields are dates, times of day, or dates with times.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum DateKind {
+    Date,
+    Time,
+    DateTime,
+}
+
+impl Default for DateKind {
+    fn default() -> DateKind {
+        DateKind::Date
+    }
+}
+
+/// A minimum or maximum date, either absolute or relative to the date of the run.
+///
+/// Serialized as a date, e.g. "1900-01-01", or as "today" optionally followed by an offset in days,
+/// weeks, months or years, e.g. "today-18y" or "today+30d".
+///
+/// ```
+/// use csv_sanity::transformers::DateBound;
+///
+/// let bound: DateBound = "today-18y".parse().unwrap();
+/// assert_eq!(bound.to_string(), "today-18y");
+/// let today = "2020-02-29".parse().unwrap();
+/// assert_eq!(bound.resolve(today), "2002-02-28".parse().ok());
+/// assert!("today+1é".parse::<DateBound>().is_err());
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum DateBound {
+    Date(NaiveDate),
+    /// An offset from the date of the run, in the specified unit: 'd', 'w', 'm' or 'y'.
+    Relative(i32, char),
+}
+
+impl DateBound {
+    /// Resolve the bound to a date, relative to the specified date of the run.
+    pub fn resolve(&self, today: NaiveDate) -> Option<NaiveDate> {
+        match *self {
+            DateBound::Date(date) => Some(date),
+            DateBound::Relative(n, unit) => {
+                let months = |n: i32| if n < 0 {
+                    today.checked_sub_months(Months::new(n.unsigned_abs()))
+                } else {
+                    today.checked_add_months(Months::new(n as u32))
+                };
+                match unit {
+                    'd' => today.checked_add_signed(Duration::days(n as i64)),
+                    'w' => today.checked_add_signed(Duration::weeks(n as i64)),
+                    'm' => months(n),
+                    'y' => months(n.checked_mul(12)?),
+                    _ => None,
+                }
+            },
+        }
+    }
+}
+
+impl Display for DateBound {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match *self {
+            DateBound::Date(date) => write!(formatter, "{}", date),
+            DateBound::Relative(0, _) => write!(formatter, "today"),
+            DateBound::Relative(n, unit) => write!(formatter, "today{:+}{}", n, unit),
+        }
+    }
+}
+
+impl FromStr for DateBound {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<DateBound, String> {
+        if s == "today" {
+            return Ok(DateBound::Relative(0, 'd'));
+        }
+        if s.starts_with("today") && s.len() > "today".len() + 1 {
+            let unit = s.chars().next_back().unwrap();
+            let offset = &s["today".len()..s.len() - unit.len_utf8()];
+            if (offset.starts_with('+') || offset.starts_with('-')) && "dwmy".contains(unit) {
+                if let Ok(n) = offset.parse() {
+                    return Ok(DateBound::Relative(n, unit));
+                }
+            }
+            return Err(format!("invalid relative date {:?}", s));
+        }
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(DateBound::Date)
+            .map_err(|e| format!("invalid date {:?}: {}", s, e))
+    }
+}
+
+impl Serialize for DateBound
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+      where S: Serializer {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateBound
+{
+    fn deserialize<D>(deserializer: D) -> Result<DateBound, D::Error>
+      where D: Deserializer<'de>
+    {
+        use serde::de::Error;
+        let string: String = Deserialize::deserialize(deserializer)?;
+        string.parse().map_err(D::Error::custom)
+    }
+}
+
+/// A parsed date, time or datetime.
+enum Parsed {
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Naive(NaiveDateTime),
+    Fixed(DateTime<FixedOffset>),
+    Zoned(DateTime<Tz>),
+}
+
+impl Parsed {
+    /// The date of a parsed date or datetime, in its own timezone if it has one.
+    fn date(&self) -> Option<NaiveDate> {
+        match *self {
+            Parsed::Date(d) => Some(d),
+            Parsed::Time(_) => None,
+            Parsed::Naive(dt) => Some(dt.date()),
+            Parsed::Fixed(dt) => Some(dt.date_naive()),
+            Parsed::Zoned(dt) => Some(dt.date_naive()),
+        }
+    }
+}
+
+/// Move the year of a date parsed from a two-digit year into the century starting at the pivot
+/// year, e.g. 1950 for 1950 to 2049.
+fn pivot_year<D: Datelike>(date: D, pivot: i32) -> Option<D> {
+    let year = pivot + (date.year() - pivot).rem_euclid(100);
+    date.with_year(year)
+}
+
+/// Swap the day and month fields of a format, e.g. "%m/%d/%Y" to "%d/%m/%Y".
+fn swap_day_month(format: &str) -> String {
+    format.replace("%d", "\0").replace("%m", "%d").replace("\0", "%m")
+}
+
+/// Common formats to try when inferring the formats of a column, in order of preference.
+fn inference_candidates(kind: DateKind, prefer_day_first: bool) -> Vec<String> {
+    let (a, b) = if prefer_day_first { ("%d", "%m") } else { ("%m", "%d") };
+    let mut dates = vec!["%Y-%m-%d".to_string(), "%Y/%m/%d".to_string(), "%Y%m%d".to_string()];
+    // Two-digit years come first, since "%Y" also accepts them, as years of the first century.
+    for year in &["%y", "%Y"] {
+        for separator in &["/", "-", "."] {
+            dates.push(format!("{}{}{}{}{}", a, separator, b, separator, year));
+            dates.push(format!("{}{}{}{}{}", b, separator, a, separator, year));
+        }
+    }
+    dates.extend(["%b %d, %Y", "%B %d, %Y", "%d %b %Y", "%d %B %Y", "%d-%b-%Y"].iter().map(|f| f.to_string()));
+
+    let times = ["%H:%M:%S%.f", "%H:%M", "%I:%M:%S %p", "%I:%M %p", "%I:%M%p"];
+    match kind {
+        DateKind::Date => dates,
+        DateKind::Time => times.iter().map(|f| f.to_string()).collect(),
+        DateKind::DateTime => {
+            let mut datetimes = Vec::new();
+            for date in dates.iter() {
+                for separator in &[" ", "T"] {
+                    for time in times.iter() {
+                        datetimes.push(format!("{}{}{}", date, separator, time));
+                    }
+                }
+            }
+            datetimes
+        },
+    }
+}
+
+fn default_input_formats() -> Vec<DateFormat> {
+    vec![DateFormat::Iso8601]
+}
+
+fn default_output_format() -> DateFormat {
+    DateFormat::Iso8601
+}
 
+/// Format a value, returning `None` if the format requires fields the value doesn't have.
+fn try_format<D: Display>(value: D) -> Option<String> {
+    let mut s = String::new();
+    write!(s, "{}", value).ok().map(|_| s)
+}
+
+/// Parse fields as dates, times or datetimes and reformat them.
+///
+/// Datetimes without a UTC offset are interpreted in `timezone` if it's set, and datetimes with a
+/// known offset or timezone are converted to `output_timezone` if it's set. Timezones are IANA
+/// timezone names, e.g. "America/New_York", resolved against a bundled timezone database.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformers::{
+///     DateTransformer,
+///     DateKind,
+/// };
+///
+/// let transformer = DateTransformer::new(vec!["%m/%d/%Y %H:%M".to_string()], "RFC3339")
+///     .with_kind(DateKind::DateTime)
+///     .with_timezone("America/New_York".parse().unwrap())
+///     .with_output_timezone("UTC".parse().unwrap());
+/// assert_eq!(transformer.transform("07/04/2017 09:30", "Signed Up", 1),
+///     Ok(Some("2017-07-04T13:30:00+00:00".to_string())));
+/// ```
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+#[serde(remote = "Self")]
 pub struct DateTransformer {
-    input_formats: Vec<String>,
-    output_format: String
+    #[serde(default = "default_input_formats")]
+    input_formats: Vec<DateFormat>,
+    #[serde(default = "default_output_format")]
+    output_format: DateFormat,
+    #[serde(default)]
+    kind: DateKind,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    timezone: Option<Tz>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    output_timezone: Option<Tz>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    min: Option<DateBound>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    max: Option<DateBound>,
+    /// Placeholder dates to reject, e.g. 1900-01-01.
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    sentinel_dates: Vec<NaiveDate>,
+    /// First year of the century that two-digit years ("%y") are in, e.g. 1950 for 1950 to 2049.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    two_digit_year_pivot: Option<i32>,
+    /// Infer the input formats of each field from a sample of its values.
+    #[serde(default)]
+    infer_formats: bool,
+    /// Prefer day-first formats when inferring formats from values that are all ambiguous.
+    #[serde(default)]
+    prefer_day_first: bool,
+    /// Input formats inferred for each field, by field name.
+    #[serde(skip)]
+    inferred_formats: BTreeMap<String, Vec<DateFormat>>,
 }
 
 impl DateTransformer {
+    /// Construct a `DateTransformer` for dates with the specified input and output formats.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the formats are invalid. See `DateFormat::parse`.
     pub fn new(input_formats: Vec<String>, output_format: &str) -> DateTransformer {
+        let parse = |format: &str| DateFormat::parse(format).unwrap_or_else(|e| panic!("{}", e));
         DateTransformer {
-            input_formats: input_formats,
-            output_format: output_format.to_string()
+            input_formats: input_formats.iter().map(|f| parse(f)).collect(),
+            output_format: parse(output_format),
+            kind: DateKind::Date,
+            timezone: None,
+            output_timezone: None,
+            min: None,
+            max: None,
+            sentinel_dates: Vec::new(),
+            two_digit_year_pivot: None,
+            infer_formats: false,
+            prefer_day_first: false,
+            inferred_formats: BTreeMap::new(),
         }
     }
 
     pub fn with_iso8601_output(input_formats: Vec<String>) -> DateTransformer {
-        Self::new(input_formats, "%F")
+        Self::new(input_formats, "ISO8601")
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the output format requires fields that values of the kind don't have, e.g. "%H"
+    /// for dates.
+    pub fn with_kind(self, kind: DateKind) -> DateTransformer {
+        let transformer = DateTransformer {
+            kind: kind,
+            .. self
+        };
+        transformer.check_output_format().unwrap_or_else(|e| panic!("{}", e));
+        transformer
+    }
+
+    /// Check that the output format can format values of the transformer's kind.
+    fn check_output_format(&self) -> Result<(), String> {
+        let format = match self.output_format {
+            DateFormat::Custom(ref f) => f.as_str(),
+            DateFormat::Iso8601 | DateFormat::Rfc3339 => return Ok(()),
+        };
+        // Whether datetimes have a UTC offset depends on the input, so only dates and times can
+        // be checked up front.
+        let formatted = match self.kind {
+            DateKind::Date => try_format(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap().format(format)),
+            DateKind::Time => try_format(NaiveTime::from_hms_opt(0, 0, 0).unwrap().format(format)),
+            DateKind::DateTime => return Ok(()),
+        };
+        match formatted {
+            Some(_) => Ok(()),
+            None => Err(format!("output format {:?} can't format values of kind {:?}", format, self.kind)),
+        }
+    }
+
+    /// Interpret datetimes without a UTC offset in the specified timezone.
+    pub fn with_timezone(self, timezone: Tz) -> DateTransformer {
+        DateTransformer {
+            timezone: Some(timezone),
+            .. self
+        }
+    }
+
+    /// Convert datetimes to the specified timezone.
+    pub fn with_output_timezone(self, output_timezone: Tz) -> DateTransformer {
+        DateTransformer {
+            output_timezone: Some(output_timezone),
+            .. self
+        }
+    }
+
+    /// Reject dates, or datetimes with dates, outside of the specified inclusive bounds.
+    ///
+    /// ```
+    /// use csv_sanity::Transformer;
+    /// use csv_sanity::transformers::DateTransformer;
+    ///
+    /// let transformer = DateTransformer::new(vec!["%m/%d/%y".to_string()], "ISO8601")
+    ///     .with_bounds(Some("2000-01-01".parse().unwrap()), Some("today".parse().unwrap()))
+    ///     .with_sentinel_dates(vec!["2001-01-01".parse().unwrap()])
+    ///     .with_two_digit_year_pivot(1980);
+    /// assert_eq!(transformer.transform("07/04/17", "Signed Up", 1), Ok(Some("2017-07-04".to_string())));
+    /// assert_eq!(transformer.transform("07/04/95", "Signed Up", 1).unwrap_err().reason,
+    ///     "before minimum date 2000-01-01");
+    /// assert_eq!(transformer.transform("01/01/01", "Signed Up", 1).unwrap_err().reason,
+    ///     "placeholder date 2001-01-01");
+    /// ```
+    pub fn with_bounds(self, min: Option<DateBound>, max: Option<DateBound>) -> DateTransformer {
+        DateTransformer {
+            min: min,
+            max: max,
+            .. self
+        }
+    }
+
+    /// Reject the specified placeholder dates.
+    pub fn with_sentinel_dates(self, sentinel_dates: Vec<NaiveDate>) -> DateTransformer {
+        DateTransformer {
+            sentinel_dates: sentinel_dates,
+            .. self
+        }
+    }
+
+    /// Interpret two-digit years as years in the century starting at the pivot year.
+    ///
+    /// Without a pivot, two-digit years 70 to 99 are in the 1900s and 00 to 69 in the 2000s.
+    pub fn with_two_digit_year_pivot(self, pivot: i32) -> DateTransformer {
+        DateTransformer {
+            two_digit_year_pivot: Some(pivot),
+            .. self
+        }
+    }
+
+    /// Infer the input formats of each field from a sample of its values, see
+    /// `infer_input_formats`.
+    ///
+    /// `prefer_day_first` picks between day-first and month-first formats if no values in the
+    /// sample have a day after the 12th.
+    pub fn with_format_inference(self, prefer_day_first: bool) -> DateTransformer {
+        DateTransformer {
+            infer_formats: true,
+            prefer_day_first: prefer_day_first,
+            .. self
+        }
+    }
+
+    /// Whether this transformer infers its input formats from a sample of values.
+    pub fn infers_formats(&self) -> bool {
+        self.infer_formats
+    }
+
+    /// Infer the most likely input formats of a sample of values from a field.
+    ///
+    /// Formats are chosen one at a time, taking the configured input formats and a list of common
+    /// formats as candidates and choosing the one that parses the most values that haven't been
+    /// parsed by an already chosen format. A day-first format is never chosen alongside the
+    /// equivalent month-first format, or vice versa, so values like "03/04/2017" are parsed
+    /// consistently.
+    ///
+    /// ```
+    /// use csv_sanity::transformers::DateTransformer;
+    ///
+    /// let transformer = DateTransformer::with_iso8601_output(vec![]).with_format_inference(false);
+    /// let formats = transformer.infer_input_formats(&["03/04/2017", "25/12/2017", "2017-12-25"]);
+    /// let formats: Vec<&str> = formats.iter().map(|f| f.as_str()).collect();
+    /// assert_eq!(formats, vec!["%d/%m/%Y", "%Y-%m-%d"]);
+    /// ```
+    pub fn infer_input_formats(&self, values: &[&str]) -> Vec<DateFormat> {
+        let mut candidates = self.input_formats.clone();
+        for format in inference_candidates(self.kind, self.prefer_day_first) {
+            let format = DateFormat::Custom(format);
+            if !candidates.contains(&format) {
+                candidates.push(format);
+            }
+        }
+
+        let mut remaining: Vec<&str> = values.to_vec();
+        let mut chosen: Vec<DateFormat> = Vec::new();
+        while !remaining.is_empty() {
+            let mut best: Option<(usize, &DateFormat)> = None;
+            for candidate in candidates.iter() {
+                let conflicts = match *candidate {
+                    DateFormat::Custom(ref f) => chosen.contains(&DateFormat::Custom(swap_day_month(f))),
+                    _ => false,
+                };
+                if chosen.contains(candidate) || conflicts {
+                    continue;
+                }
+                let n_parsed = remaining.iter().filter(|v| self.parse_with(v, candidate).is_some()).count();
+                if n_parsed > best.map_or(0, |(n, _)| n) {
+                    best = Some((n_parsed, candidate));
+                }
+            }
+            match best {
+                Some((_, format)) => {
+                    remaining.retain(|v| self.parse_with(v, format).is_none());
+                    chosen.push(format.clone());
+                },
+                None => break,
+            }
+        }
+        chosen
+    }
+
+    /// Use the specified input formats for the field with the specified name, instead of the
+    /// configured input formats.
+    pub fn set_inferred_formats(&mut self, field_name: &str, formats: Vec<DateFormat>) {
+        self.inferred_formats.insert(field_name.to_string(), formats);
+    }
+
+    fn parse_with(&self, value: &str, format: &DateFormat) -> Option<Parsed> {
+        let custom = match *format {
+            DateFormat::Custom(ref f) => Some(f.as_str()),
+            _ => None,
+        };
+        let parsed = self.parse_as_kind(value, format, custom)?;
+        match (self.two_digit_year_pivot, custom) {
+            (Some(pivot), Some(f)) if f.contains("%y") => match parsed {
+                Parsed::Date(d) => pivot_year(d, pivot).map(Parsed::Date),
+                Parsed::Naive(dt) => pivot_year(dt, pivot).map(Parsed::Naive),
+                Parsed::Fixed(dt) => pivot_year(dt, pivot).map(Parsed::Fixed),
+                parsed => Some(parsed),
+            },
+            _ => Some(parsed),
+        }
+    }
+
+    fn parse_as_kind(&self, value: &str, format: &DateFormat, custom: Option<&str>) -> Option<Parsed> {
+        match self.kind {
+            DateKind::Date => NaiveDate::parse_from_str(value, custom.unwrap_or("%Y-%m-%d"))
+                .ok().map(Parsed::Date),
+            DateKind::Time => NaiveTime::parse_from_str(value, custom.unwrap_or("%H:%M:%S%.f"))
+                .ok().map(Parsed::Time),
+            DateKind::DateTime => {
+                let fixed = match custom {
+                    Some(f) => DateTime::parse_from_str(value, f),
+                    None => DateTime::parse_from_rfc3339(value),
+                };
+                if let Ok(datetime) = fixed {
+                    return Some(Parsed::Fixed(datetime));
+                }
+                if *format == DateFormat::Rfc3339 {
+                    return None;
+                }
+                NaiveDateTime::parse_from_str(value, custom.unwrap_or("%Y-%m-%dT%H:%M:%S%.f"))
+                    .ok().map(Parsed::Naive)
+            },
+        }
+    }
+
+    /// Apply the input and output timezones to a parsed datetime.
+    fn convert(&self, parsed: Parsed) -> Result<Parsed, String> {
+        let parsed = match (parsed, self.timezone) {
+            (Parsed::Naive(naive), Some(timezone)) => match timezone.from_local_datetime(&naive) {
+                LocalResult::Single(datetime) => Parsed::Zoned(datetime),
+                LocalResult::Ambiguous(_, _) => return Err(format!("ambiguous local time in {}", timezone.name())),
+                LocalResult::None => return Err(format!("nonexistent local time in {}", timezone.name())),
+            },
+            (parsed, _) => parsed,
+        };
+        match (parsed, self.output_timezone) {
+            (Parsed::Fixed(datetime), Some(output_timezone)) => Ok(Parsed::Zoned(datetime.with_timezone(&output_timezone))),
+            (Parsed::Zoned(datetime), Some(output_timezone)) => Ok(Parsed::Zoned(datetime.with_timezone(&output_timezone))),
+            (Parsed::Naive(_), Some(_)) => Err("no timezone to convert from".to_string()),
+            (parsed, _) => Ok(parsed),
+        }
+    }
+
+    /// Check the date of a parsed date or datetime against the bounds and sentinel dates.
+    fn check_date(&self, parsed: &Parsed) -> Result<(), String> {
+        let date = match parsed.date() {
+            Some(d) => d,
+            None => return Ok(()),
+        };
+        if self.sentinel_dates.contains(&date) {
+            return Err(format!("placeholder date {}", date));
+        }
+        if let Some(min) = self.min.and_then(|b| b.resolve(*RUN_DATE)) {
+            if date < min {
+                return Err(format!("before minimum date {}", min));
+            }
+        }
+        if let Some(max) = self.max.and_then(|b| b.resolve(*RUN_DATE)) {
+            if date > max {
+                return Err(format!("after maximum date {}", max));
+            }
+        }
+        Ok(())
+    }
+
+    fn format(&self, parsed: &Parsed) -> Result<String, String> {
+        let format = match self.output_format {
+            DateFormat::Custom(ref f) => f.as_str(),
+            DateFormat::Iso8601 | DateFormat::Rfc3339 => match *parsed {
+                Parsed::Date(_) => "%Y-%m-%d",
+                Parsed::Time(_) => "%H:%M:%S%.f",
+                Parsed::Naive(_) if self.output_format == DateFormat::Rfc3339 => {
+                    return Err("no UTC offset for RFC 3339 output".to_string());
+                },
+                Parsed::Naive(_) => "%Y-%m-%dT%H:%M:%S%.f",
+                Parsed::Fixed(_) | Parsed::Zoned(_) => "%Y-%m-%dT%H:%M:%S%.f%:z",
+            },
+        };
+        let formatted = match *parsed {
+            Parsed::Date(ref d) => try_format(d.format(format)),
+            Parsed::Time(ref t) => try_format(t.format(format)),
+            Parsed::Naive(ref dt) => try_format(dt.format(format)),
+            Parsed::Fixed(ref dt) => try_format(dt.format(format)),
+            Parsed::Zoned(ref dt) => try_format(dt.format(format)),
+        };
+        formatted.ok_or_else(|| format!("unable to format with output format {:?}", format))
+    }
+}
+
+impl Serialize for DateTransformer
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+      where S: Serializer {
+        DateTransformer::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateTransformer
+{
+    fn deserialize<D>(deserializer: D) -> Result<DateTransformer, D::Error>
+      where D: Deserializer<'de>
+    {
+        use serde::de::Error;
+        let transformer = DateTransformer::deserialize(deserializer)?;
+        transformer.check_output_format().map_err(D::Error::custom)?;
+        Ok(transformer)
     }
 }
 
 impl Transformer for DateTransformer {
     fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
-        for format in self.input_formats.iter() {
-            if let Ok(time) = strptime(field_value, &format) {
-                return TransformResult::present(
-                    &format!("{}", time.strftime(&self.output_format).unwrap())
-                );
-            }
+        let input_formats = self.inferred_formats.get(field_name).unwrap_or(&self.input_formats);
+        let parsed = match input_formats.iter().filter_map(|f| self.parse_with(field_value, f)).next() {
+            Some(p) => p,
+            None => {
+                let reason = match self.kind {
+                    DateKind::Date => "unable to parse as date",
+                    DateKind::Time => "unable to parse as time",
+                    DateKind::DateTime => "unable to parse as datetime",
+                };
+                return TransformResult::error(field_value, field_name, record_n, reason);
+            },
+        };
+        let result = self.convert(parsed)
+            .and_then(|p| self.check_date(&p).map(|_| p))
+            .and_then(|p| self.format(&p));
+        match result {
+            Ok(formatted) => TransformResult::present(&formatted),
+            Err(reason) => TransformResult::error(field_value, field_name, record_n, &reason),
         }
-        TransformResult::error(field_value, field_name, record_n, "unable to parse as date")
     }
 }
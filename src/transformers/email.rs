@@ -4,27 +4,227 @@ use transformer::{
     TransformResult
 };
 
-use regex::Regex;
+use idna;
 
-lazy_static! {
-    static ref EMAIL_REGEX: Regex = Regex::new(r"(?i)\A[a-z0-9!#$%&'*+/=?^_`{|}~-]+(?:\.[a-z0-9!#$%&'*+/=?^_`{|}~-]+)*@(?:[a-z0-9](?:[a-z0-9-]*[a-z0-9])?\.)+[a-z0-9](?:[a-z0-9-]*[a-z0-9])?\z").unwrap();
+/// Misspellings of common email domains and their corrections.
+///
+/// Only misspelled provider names, e.g. "gmial.com", and top-level domains that don't exist, e.g.
+/// "gmail.con", are listed. Domains that differ from a provider's only by a real top-level domain,
+/// like "gmail.co" or "comcast.com", are valid addresses in their own right and aren't corrected.
+static DOMAIN_TYPOS: &'static [(&'static str, &'static str)] = &[
+    ("gmial.com", "gmail.com"), ("gmai.com", "gmail.com"), ("gamil.com", "gmail.com"),
+    ("gnail.com", "gmail.com"), ("gmaill.com", "gmail.com"), ("gmail.con", "gmail.com"),
+    ("gmail.cmo", "gmail.com"), ("gmail.comm", "gmail.com"),
+    ("googlemail.con", "googlemail.com"),
+    ("hotmial.com", "hotmail.com"), ("hotmal.com", "hotmail.com"), ("hotmai.com", "hotmail.com"),
+    ("homail.com", "hotmail.com"), ("hotmail.con", "hotmail.com"), ("hotmail.cmo", "hotmail.com"),
+    ("yaho.com", "yahoo.com"), ("yahooo.com", "yahoo.com"), ("yhoo.com", "yahoo.com"),
+    ("yahoo.con", "yahoo.com"), ("yahoo.cmo", "yahoo.com"),
+    ("outlok.com", "outlook.com"), ("outloo.com", "outlook.com"), ("outlook.con", "outlook.com"),
+    ("iclod.com", "icloud.com"), ("icoud.com", "icloud.com"), ("icloud.con", "icloud.com"),
+    ("aol.con", "aol.com"), ("comcast.nte", "comcast.net"),
+];
+
+/// Domains of disposable email services.
+static DISPOSABLE_DOMAINS: &'static [&'static str] = &[
+    "10minutemail.com", "burnermail.io", "discard.email", "dispostable.com", "emailondeck.com",
+    "fakeinbox.com", "getairmail.com", "getnada.com", "guerrillamail.com", "guerrillamail.net",
+    "guerrillamailblock.com", "maildrop.cc", "mailinator.com", "mailnesia.com", "mintemail.com",
+    "mohmal.com", "mytemp.email", "sharklasers.com", "spamgourmet.com", "temp-mail.org",
+    "tempmail.com", "tempr.email", "throwawaymail.com", "trashmail.com", "yopmail.com",
+];
+
+/// Whether a character is allowed in the local part of an email address outside of quotes.
+fn is_local_part_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~-".contains(c)
 }
 
+/// Validate email addresses, giving specific reasons for rejecting them, and normalize them.
+///
+/// By default the whole address is lowercased. Internationalized domain names are accepted and
+/// output in their normalized Unicode form, or as punycode with `with_ascii_domains`.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformers::EmailTransformer;
+///
+/// let transformer = EmailTransformer::new().with_fixups().preserving_local_case();
+/// assert_eq!(transformer.transform(" John.Smith@GMIAL.com, ", "Email", 1), Ok(Some("John.Smith@gmail.com".to_string())));
+/// assert_eq!(transformer.transform("jane@gmail.co", "Email", 1), Ok(Some("jane@gmail.co".to_string())));
+/// assert_eq!(transformer.transform("john.smith.example.com", "Email", 1).unwrap_err().reason, "missing @");
+/// assert_eq!(transformer.transform("john@bücher.example", "Email", 1), Ok(Some("john@bücher.example".to_string())));
+/// ```
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
-pub struct EmailTransformer {}
+pub struct EmailTransformer {
+    /// Fix common mistakes: whitespace, "mailto:" prefixes, stray trailing punctuation and
+    /// misspellings of common domains.
+    #[serde(default)]
+    fix_mistakes: bool,
+    /// Only lowercase the domain, leaving the local part as given.
+    #[serde(default)]
+    preserve_local_case: bool,
+    /// Output internationalized domains as punycode, e.g. "xn--bcher-kva.example".
+    #[serde(default)]
+    ascii_domains: bool,
+    /// Reject addresses at disposable email services.
+    #[serde(default)]
+    reject_disposable: bool,
+}
 
 impl EmailTransformer {
     pub fn new() -> EmailTransformer {
-        EmailTransformer {}
+        EmailTransformer {
+            fix_mistakes: false,
+            preserve_local_case: false,
+            ascii_domains: false,
+            reject_disposable: false,
+        }
+    }
+
+    /// Fix common mistakes: whitespace, "mailto:" prefixes, stray trailing punctuation and
+    /// misspellings of common domains, e.g. "gmial.com".
+    pub fn with_fixups(self) -> EmailTransformer {
+        EmailTransformer {
+            fix_mistakes: true,
+            .. self
+        }
+    }
+
+    pub fn preserving_local_case(self) -> EmailTransformer {
+        EmailTransformer {
+            preserve_local_case: true,
+            .. self
+        }
+    }
+
+    pub fn with_ascii_domains(self) -> EmailTransformer {
+        EmailTransformer {
+            ascii_domains: true,
+            .. self
+        }
+    }
+
+    /// Reject addresses at disposable email services, according to a bundled list of domains.
+    ///
+    /// ```
+    /// use csv_sanity::Transformer;
+    /// use csv_sanity::transformers::EmailTransformer;
+    ///
+    /// let transformer = EmailTransformer::new().rejecting_disposable_domains();
+    /// assert_eq!(transformer.transform("someone@mailinator.com", "Email", 1).unwrap_err().reason,
+    ///     "disposable email domain mailinator.com");
+    /// ```
+    pub fn rejecting_disposable_domains(self) -> EmailTransformer {
+        EmailTransformer {
+            reject_disposable: true,
+            .. self
+        }
+    }
+
+    fn fix(&self, value: &str) -> String {
+        let value: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+        let has_mailto = value.get(..7).map(|prefix| prefix.eq_ignore_ascii_case("mailto:")).unwrap_or(false);
+        let value = if has_mailto { &value[7..] } else { &value[..] };
+        value.trim_matches(|c| c == ',' || c == ';' || c == '.' || c == '<' || c == '>').to_string()
+    }
+
+    fn normalize(&self, value: &str) -> Result<String, String> {
+        let at = match value.rfind('@') {
+            Some(at) => at,
+            None => return Err("missing @".to_string()),
+        };
+        let (local_part, domain) = (&value[..at], &value[at + 1..]);
+        if local_part.contains('@') {
+            return Err("more than one @".to_string());
+        }
+        check_local_part(local_part)?;
+
+        let domain = check_domain(domain)?;
+        let domain = if self.fix_mistakes {
+            DOMAIN_TYPOS.iter()
+                .find(|&&(typo, _)| typo == domain)
+                .map(|&(_, correction)| correction.to_string())
+                .unwrap_or(domain)
+        } else {
+            domain
+        };
+        if self.reject_disposable {
+            let is_disposable = DISPOSABLE_DOMAINS.iter().any(|&d| {
+                domain == d || (domain.ends_with(d) && domain[..domain.len() - d.len()].ends_with('.'))
+            });
+            if is_disposable {
+                return Err(format!("disposable email domain {}", domain));
+            }
+        }
+
+        let local_part = if self.preserve_local_case { local_part.to_string() } else { local_part.to_lowercase() };
+        let domain = if self.ascii_domains { domain } else { idna::domain_to_unicode(&domain).0 };
+        Ok(format!("{}@{}", local_part, domain))
+    }
+}
+
+fn check_local_part(local_part: &str) -> Result<(), String> {
+    if local_part.is_empty() {
+        return Err("missing local part before @".to_string());
+    }
+    if local_part.len() > 64 {
+        return Err("local part longer than 64 characters".to_string());
+    }
+    if local_part.starts_with('.') || local_part.ends_with('.') {
+        return Err("local part starts or ends with a dot".to_string());
+    }
+    if local_part.contains("..") {
+        return Err("consecutive dots in local part".to_string());
+    }
+    match local_part.chars().find(|&c| c != '.' && !is_local_part_char(c)) {
+        Some(c) => Err(format!("invalid character {:?} in local part", c)),
+        None => Ok(()),
+    }
+}
+
+/// Check a domain, returning its lowercased ASCII form.
+fn check_domain(domain: &str) -> Result<String, String> {
+    if domain.is_empty() {
+        return Err("missing domain after @".to_string());
+    }
+    if domain.ends_with('.') {
+        return Err("domain ends with a dot".to_string());
     }
+    if domain.starts_with('.') || domain.contains("..") {
+        return Err("empty label in domain".to_string());
+    }
+    let ascii_domain = if domain.is_ascii() {
+        domain.to_lowercase()
+    } else {
+        idna::domain_to_ascii(domain).map_err(|_| "not a valid internationalized domain".to_string())?
+    };
+    let labels: Vec<&str> = ascii_domain.split('.').collect();
+    if labels.len() < 2 {
+        return Err("domain has no top-level domain".to_string());
+    }
+    for label in labels.iter() {
+        if label.len() > 63 {
+            return Err("domain label longer than 63 characters".to_string());
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err("domain label starts or ends with a hyphen".to_string());
+        }
+        if let Some(c) = label.chars().find(|&c| !c.is_ascii_alphanumeric() && c != '-') {
+            return Err(format!("invalid character {:?} in domain", c));
+        }
+    }
+    if labels[labels.len() - 1].chars().all(|c| c.is_ascii_digit()) {
+        return Err("top-level domain is numeric".to_string());
+    }
+    Ok(ascii_domain)
 }
 
 impl Transformer for EmailTransformer {
     fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
-        if EMAIL_REGEX.is_match(field_value) {
-            TransformResult::present(&field_value.to_lowercase())
-        } else {
-            TransformResult::error(field_value, field_name, record_n, "invalid email address")
+        let value = if self.fix_mistakes { self.fix(field_value) } else { field_value.to_string() };
+        match self.normalize(&value) {
+            Ok(email) => TransformResult::present(&email),
+            Err(reason) => TransformResult::error(field_value, field_name, record_n, &reason),
         }
     }
 }
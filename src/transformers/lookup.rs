@@ -0,0 +1,151 @@
+use Transformer;
+use transformer::{
+    TransformResultHelper,
+    TransformResult
+};
+
+use csv;
+use serde::{
+    Serialize,
+    Serializer,
+    Deserialize,
+    Deserializer,
+};
+use std::collections::HashMap;
+use std::hash::{
+    Hash,
+    Hasher,
+};
+use std::sync::Arc;
+
+/// Load the key column, and optionally a value column, from a CSV file with headers.
+///
+/// Returns pairs of keys and values, with the key as the value if no value column is specified.
+pub fn load_table(file: &str, delimiter: char, key_column: &str, value_column: Option<&str>) -> Result<Vec<(String, String)>, String> {
+    let mut delimiter_bytes = [0; 4];
+    if delimiter.encode_utf8(&mut delimiter_bytes).len() != 1 {
+        return Err(format!("delimiter {:?} is not a single byte character", delimiter));
+    }
+    let mut reader = csv::Reader::from_file(file)
+        .map_err(|e| format!("unable to read {}: {}", file, e))?
+        .has_headers(true)
+        .delimiter(delimiter_bytes[0]);
+    let headers = reader.headers().map_err(|e| format!("unable to read headers from {}: {}", file, e))?;
+    let column_n = |column: &str| {
+        headers.iter().position(|h| h == column)
+            .ok_or_else(|| format!("column {} not found in {}", column, file))
+    };
+    let key_n = column_n(key_column)?;
+    let value_n = match value_column {
+        Some(column) => column_n(column)?,
+        None => key_n,
+    };
+
+    let mut table = Vec::new();
+    for (record_n, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| format!("unable to read record {} of {}: {}", record_n + 2, file, e))?;
+        match (record.get(key_n), record.get(value_n)) {
+            (Some(key), Some(value)) => table.push((key.clone(), value.clone())),
+            _ => return Err(format!("record {} of {} is missing columns", record_n + 2, file)),
+        }
+    }
+    Ok(table)
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+struct LookupConfig {
+    file: String,
+    key_column: String,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    value_column: Option<String>,
+    #[serde(default = "default_delimiter")]
+    delimiter: char,
+}
+
+/// Validate fields against the key column of a lookup CSV file, optionally replacing them with
+/// the value in another column of the same file.
+///
+/// The lookup file is read once, when the transformer is constructed or deserialized.
+#[derive(Clone, Debug)]
+pub struct LookupTransformer {
+    config: LookupConfig,
+    table: Arc<HashMap<String, String>>,
+}
+
+impl LookupTransformer {
+    /// Load a lookup table from the key column of a CSV file, validating fields against it.
+    pub fn validating(file: &str, key_column: &str) -> Result<LookupTransformer, String> {
+        Self::load(LookupConfig {
+            file: file.to_string(),
+            key_column: key_column.to_string(),
+            value_column: None,
+            delimiter: default_delimiter(),
+        })
+    }
+
+    /// Load a lookup table from the key and value columns of a CSV file, replacing fields with the
+    /// value for their key.
+    pub fn mapping(file: &str, key_column: &str, value_column: &str) -> Result<LookupTransformer, String> {
+        Self::load(LookupConfig {
+            file: file.to_string(),
+            key_column: key_column.to_string(),
+            value_column: Some(value_column.to_string()),
+            delimiter: default_delimiter(),
+        })
+    }
+
+    fn load(config: LookupConfig) -> Result<LookupTransformer, String> {
+        let table = load_table(&config.file, config.delimiter, &config.key_column, config.value_column.as_ref().map(|s| s.as_str()))?;
+        Ok(LookupTransformer {
+            config: config,
+            table: Arc::new(table.into_iter().collect()),
+        })
+    }
+}
+
+impl PartialEq for LookupTransformer {
+    fn eq(&self, other: &LookupTransformer) -> bool {
+        self.config == other.config
+    }
+}
+
+impl Eq for LookupTransformer {}
+
+impl Hash for LookupTransformer {
+    fn hash<H>(&self, state: &mut H)
+        where H: Hasher {
+        self.config.hash(state);
+    }
+}
+
+impl Serialize for LookupTransformer
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+      where S: Serializer {
+        self.config.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for LookupTransformer
+{
+    fn deserialize<D>(deserializer: D) -> Result<LookupTransformer, D::Error>
+      where D: Deserializer<'de>
+    {
+        use serde::de::Error;
+        let config = LookupConfig::deserialize(deserializer)?;
+        LookupTransformer::load(config).map_err(D::Error::custom)
+    }
+}
+
+impl Transformer for LookupTransformer {
+    fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
+        match self.table.get(field_value) {
+            Some(value) => TransformResult::present(value),
+            None => TransformResult::error(field_value, field_name, record_n, &format!("not found in {}", self.config.file)),
+        }
+    }
+}
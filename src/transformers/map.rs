@@ -0,0 +1,229 @@
+use Transformer;
+use transformer::{
+    TransformResultHelper,
+    TransformResult
+};
+use super::lookup::load_table;
+
+use serde::{
+    Serialize,
+    Serializer,
+    Deserialize,
+    Deserializer,
+};
+use std::collections::{
+    BTreeMap,
+    HashMap,
+};
+use std::hash::{
+    Hash,
+    Hasher,
+};
+use std::sync::Arc;
+
+/// What to do with fields that aren't found in the mapping table.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Unmapped {
+    /// Leave the field unchanged.
+    PassThrough,
+    /// Replace the field with an empty value.
+    Null,
+    /// Reject the field.
+    Error,
+}
+
+impl Default for Unmapped {
+    fn default() -> Unmapped {
+        Unmapped::Error
+    }
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+struct MapFile {
+    file: String,
+    key_column: String,
+    value_column: String,
+    #[serde(default = "default_delimiter")]
+    delimiter: char,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+struct MapConfig {
+    #[serde(default, skip_serializing_if="BTreeMap::is_empty")]
+    mapping: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    file: Option<MapFile>,
+    #[serde(default)]
+    case_insensitive: bool,
+    #[serde(default)]
+    whitespace_insensitive: bool,
+    #[serde(default)]
+    unmapped: Unmapped,
+}
+
+/// Replace fields according to a mapping table of synonyms, e.g. mapping "Calif." and
+/// "california" to "CA".
+///
+/// The mapping table is built from inline entries and, optionally, two columns of a CSV file.
+/// Inline entries take precedence over entries from the file.
+#[derive(Clone, Debug)]
+pub struct MapTransformer {
+    config: MapConfig,
+    file_entries: Arc<Vec<(String, String)>>,
+    table: Arc<HashMap<String, String>>,
+}
+
+impl MapTransformer {
+    /// Construct a `MapTransformer` with an inline mapping table.
+    ///
+    /// ```
+    /// use csv_sanity::Transformer;
+    /// use csv_sanity::transformers::{
+    ///     MapTransformer,
+    ///     Unmapped,
+    /// };
+    ///
+    /// let mapping = vec![("calif.", "CA"), ("california", "CA")].into_iter()
+    ///     .map(|(k, v)| (k.to_string(), v.to_string()))
+    ///     .collect();
+    /// let transformer = MapTransformer::new(mapping)
+    ///     .case_insensitive()
+    ///     .whitespace_insensitive()
+    ///     .unmapped(Unmapped::PassThrough);
+    /// assert_eq!(transformer.transform(" Cali fornia", "State", 1), Ok(Some("CA".to_string())));
+    /// assert_eq!(transformer.transform("NV", "State", 1), Ok(Some("NV".to_string())));
+    /// ```
+    pub fn new(mapping: BTreeMap<String, String>) -> MapTransformer {
+        Self::build(MapConfig {
+            mapping: mapping,
+            file: None,
+            case_insensitive: false,
+            whitespace_insensitive: false,
+            unmapped: Default::default(),
+        }, Vec::new())
+    }
+
+    /// Construct a `MapTransformer` with a mapping table read from two columns of a CSV file with
+    /// headers.
+    pub fn from_file(file: &str, key_column: &str, value_column: &str) -> Result<MapTransformer, String> {
+        Self::load(MapConfig {
+            mapping: BTreeMap::new(),
+            file: Some(MapFile {
+                file: file.to_string(),
+                key_column: key_column.to_string(),
+                value_column: value_column.to_string(),
+                delimiter: default_delimiter(),
+            }),
+            case_insensitive: false,
+            whitespace_insensitive: false,
+            unmapped: Default::default(),
+        })
+    }
+
+    /// Match fields against the mapping table regardless of case.
+    pub fn case_insensitive(self) -> MapTransformer {
+        let config = MapConfig { case_insensitive: true, .. self.config };
+        Self::build(config, self.file_entries.as_ref().clone())
+    }
+
+    /// Match fields against the mapping table ignoring all whitespace.
+    pub fn whitespace_insensitive(self) -> MapTransformer {
+        let config = MapConfig { whitespace_insensitive: true, .. self.config };
+        Self::build(config, self.file_entries.as_ref().clone())
+    }
+
+    /// Set what to do with fields that aren't found in the mapping table. See `Unmapped`.
+    pub fn unmapped(self, unmapped: Unmapped) -> MapTransformer {
+        MapTransformer {
+            config: MapConfig { unmapped: unmapped, .. self.config },
+            .. self
+        }
+    }
+
+    fn load(config: MapConfig) -> Result<MapTransformer, String> {
+        let file_entries = match config.file {
+            Some(ref f) => load_table(&f.file, f.delimiter, &f.key_column, Some(&f.value_column))?,
+            None => Vec::new(),
+        };
+        Ok(Self::build(config, file_entries))
+    }
+
+    fn build(config: MapConfig, file_entries: Vec<(String, String)>) -> MapTransformer {
+        let mut table = HashMap::new();
+        for &(ref key, ref value) in file_entries.iter() {
+            table.insert(normalize(&config, key), value.clone());
+        }
+        for (key, value) in config.mapping.iter() {
+            table.insert(normalize(&config, key), value.clone());
+        }
+        MapTransformer {
+            config: config,
+            file_entries: Arc::new(file_entries),
+            table: Arc::new(table),
+        }
+    }
+}
+
+fn normalize(config: &MapConfig, key: &str) -> String {
+    let key = if config.whitespace_insensitive {
+        key.chars().filter(|c| !c.is_whitespace()).collect()
+    } else {
+        key.to_string()
+    };
+    if config.case_insensitive {
+        key.to_lowercase()
+    } else {
+        key
+    }
+}
+
+impl PartialEq for MapTransformer {
+    fn eq(&self, other: &MapTransformer) -> bool {
+        self.config == other.config
+    }
+}
+
+impl Eq for MapTransformer {}
+
+impl Hash for MapTransformer {
+    fn hash<H>(&self, state: &mut H)
+        where H: Hasher {
+        self.config.hash(state);
+    }
+}
+
+impl Serialize for MapTransformer
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+      where S: Serializer {
+        self.config.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MapTransformer
+{
+    fn deserialize<D>(deserializer: D) -> Result<MapTransformer, D::Error>
+      where D: Deserializer<'de>
+    {
+        use serde::de::Error;
+        let config = MapConfig::deserialize(deserializer)?;
+        MapTransformer::load(config).map_err(D::Error::custom)
+    }
+}
+
+impl Transformer for MapTransformer {
+    fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
+        match self.table.get(&normalize(&self.config, field_value)) {
+            Some(value) => TransformResult::present(value),
+            None => match self.config.unmapped {
+                Unmapped::PassThrough => TransformResult::present(field_value),
+                Unmapped::Null => TransformResult::excluded(),
+                Unmapped::Error => TransformResult::error(field_value, field_name, record_n, "no mapping for value"),
+            },
+        }
+    }
+}
@@ -1,10 +1,21 @@
 use transformer::{
     Transformer,
     TransformResult,
+    InputRecord,
 };
 
 mod trim;
-pub use self::trim::TrimTransformer;
+pub use self::trim::{
+    TrimTransformer,
+    TrimSide,
+};
+
+mod normalize;
+pub use self::normalize::{
+    NormalizeTransformer,
+    NormalForm,
+    transliterate,
+};
 
 mod none;
 pub use self::none::NoneTransformer;
@@ -18,17 +29,43 @@ pub use self::regex::{
 mod capitalize;
 pub use self::capitalize::{
     CapitalizeTransformer,
-    capitalize
+    CapitalizeMode,
+    capitalize,
+    capitalize_name,
+};
+
+mod case;
+pub use self::case::{
+    CaseTransformer,
+    Case,
+    convert_case,
 };
 
 mod email;
 pub use self::email::EmailTransformer;
 
 mod number;
-pub use self::number::NumberTransformer;
+pub use self::number::{
+    NumberTransformer,
+    NumberKind,
+    Decimal,
+};
+
+mod currency;
+pub use self::currency::{
+    CurrencyTransformer,
+    CurrencyFormat,
+    CurrencyOutput,
+    Money,
+};
 
 mod date;
-pub use self::date::DateTransformer;
+pub use self::date::{
+    DateTransformer,
+    DateFormat,
+    DateKind,
+    DateBound,
+};
 
 mod choice;
 pub use self::choice::ChoiceTransformer;
@@ -37,40 +74,93 @@ mod zipcode;
 pub use self::zipcode::ZipcodeTransformer;
 
 mod phone_number;
-pub use self::phone_number::PhoneNumberTransformer;
+pub use self::phone_number::{
+    PhoneNumberTransformer,
+    PhoneNumberFormat,
+    Extensions,
+    Region,
+};
+
+mod state;
+pub use self::state::{
+    StateTransformer,
+    StateOutput,
+};
+
+mod country;
+pub use self::country::{
+    CountryTransformer,
+    CountryOutput,
+    country_code,
+};
+
+mod postal_code;
+pub use self::postal_code::PostalCodeTransformer;
+
+mod lookup;
+pub use self::lookup::LookupTransformer;
+
+mod map;
+pub use self::map::{
+    MapTransformer,
+    Unmapped,
+};
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
 pub enum Transformers
 {
     Trim(TrimTransformer),
     None(NoneTransformer),
+    Normalize(NormalizeTransformer),
     Regex(RegexTransformer),
     RegexMatch(RegexMatchTransformer),
     Capitalize(CapitalizeTransformer),
+    Case(CaseTransformer),
     Email(EmailTransformer),
     Number(NumberTransformer),
+    Currency(CurrencyTransformer),
     Date(DateTransformer),
     Choice(ChoiceTransformer),
     Zipcode(ZipcodeTransformer),
     PhoneNumber(PhoneNumberTransformer),
+    PostalCode(PostalCodeTransformer),
+    State(StateTransformer),
+    Country(CountryTransformer),
+    Lookup(LookupTransformer),
+    Map(MapTransformer),
 }
 
 impl Transformer for Transformers {
+    fn transform_in_record(&self, field_value: &str, field_name: &str, record: &InputRecord, record_n: usize) -> TransformResult {
+        match *self {
+            Transformers::PostalCode(ref t) => t.transform_in_record(field_value, field_name, record, record_n),
+            _ => self.transform(field_value, field_name, record_n),
+        }
+    }
+
     fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
         use self::Transformers::*;
 
         match *self {
             Trim(ref t) => t.transform(field_value, field_name, record_n),
             None(ref t) => t.transform(field_value, field_name, record_n),
+            Normalize(ref t) => t.transform(field_value, field_name, record_n),
             Regex(ref t) => t.transform(field_value, field_name, record_n),
             RegexMatch(ref t) => t.transform(field_value, field_name, record_n),
             Capitalize(ref t) => t.transform(field_value, field_name, record_n),
+            Case(ref t) => t.transform(field_value, field_name, record_n),
             Email(ref t) => t.transform(field_value, field_name, record_n),
             Number(ref t) => t.transform(field_value, field_name, record_n),
+            Currency(ref t) => t.transform(field_value, field_name, record_n),
             Date(ref t) => t.transform(field_value, field_name, record_n),
             Choice(ref t) => t.transform(field_value, field_name, record_n),
             Zipcode(ref t) => t.transform(field_value, field_name, record_n),
-            PhoneNumber(ref t) => t.transform(field_value, field_name, record_n)
+            PhoneNumber(ref t) => t.transform(field_value, field_name, record_n),
+            PostalCode(ref t) => t.transform(field_value, field_name, record_n),
+            State(ref t) => t.transform(field_value, field_name, record_n),
+            Country(ref t) => t.transform(field_value, field_name, record_n),
+            Lookup(ref t) => t.transform(field_value, field_name, record_n),
+            Map(ref t) => t.transform(field_value, field_name, record_n)
         }
     }
 }
@@ -8,24 +8,97 @@ use newtypes::Regex;
 use regex;
 
 
+/// Placeholders for missing values commonly written by databases, spreadsheets and data analysis
+/// tools, matched regardless of case.
+static STANDARD_SENTINELS: &'static [&'static str] = &[
+    "", "null", "(null)", "<null>", "#null!", "\\n", "nil", "none", "n/a", "#n/a", "#n/a n/a", "na",
+    "<na>", "#na", "nan", "-nan", "-", "--", "1.#ind", "-1.#ind", "1.#qnan", "-1.#qnan",
+];
+
+/// Replace fields matching a regex, or one of a list of sentinel values, with an empty value.
+///
+/// Sentinels are compared with fields regardless of case and surrounding whitespace.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformers::NoneTransformer;
+///
+/// let transformer = NoneTransformer::with_blank_matcher().with_standard_sentinels()
+///     .with_sentinels(vec!["unknown".to_string()]);
+/// assert_eq!(transformer.transform(" ", "Phone", 1), Ok(None));
+/// assert_eq!(transformer.transform("#N/A", "Phone", 1), Ok(None));
+/// assert_eq!(transformer.transform("Unknown", "Phone", 1), Ok(None));
+/// assert_eq!(transformer.transform("555-0123", "Phone", 1), Ok(Some("555-0123".to_string())));
+///
+/// let transformer = NoneTransformer::matching_sentinels(vec!["unknown".to_string()])
+///     .with_sentinels(vec!["TBD".to_string()]);
+/// assert_eq!(transformer.transform("Unknown", "Phone", 1), Ok(None));
+/// assert_eq!(transformer.transform("tbd", "Phone", 1), Ok(None));
+/// ```
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
 pub struct NoneTransformer {
-    regex: Regex
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    regex: Option<Regex>,
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    sentinels: Vec<String>,
+    /// Also match the standard sentinels, e.g. "NULL", "N/A" and "nan".
+    #[serde(default)]
+    standard_sentinels: bool,
 }
 
 impl NoneTransformer {
     pub fn new(regex: regex::Regex) -> NoneTransformer {
-        NoneTransformer { regex: Regex::from(regex) }
+        NoneTransformer {
+            regex: Some(Regex::from(regex)),
+            sentinels: Vec::new(),
+            standard_sentinels: false,
+        }
     }
 
     pub fn with_blank_matcher() -> NoneTransformer {
         Self::new(regex::Regex::new(r"\A(?:[:cntrl:]|\s)*\z").unwrap())
     }
+
+    /// Construct a `NoneTransformer` that only matches the specified sentinel values.
+    pub fn matching_sentinels(sentinels: Vec<String>) -> NoneTransformer {
+        NoneTransformer {
+            regex: None,
+            sentinels: sentinels,
+            standard_sentinels: false,
+        }
+    }
+
+    /// Also match the specified sentinel values.
+    pub fn with_sentinels(mut self, sentinels: Vec<String>) -> NoneTransformer {
+        self.sentinels.extend(sentinels);
+        self
+    }
+
+    /// Also match the standard sentinels, e.g. "NULL", "N/A", "#N/A", "-" and "nan".
+    ///
+    /// Some of these also occur in real data, e.g. "NA" is the country code of Namibia and "-" may
+    /// be a deliberate value, so only use them for fields where they can't be meaningful.
+    pub fn with_standard_sentinels(self) -> NoneTransformer {
+        NoneTransformer {
+            standard_sentinels: true,
+            .. self
+        }
+    }
+
+    fn is_sentinel(&self, field_value: &str) -> bool {
+        if self.sentinels.is_empty() && !self.standard_sentinels {
+            return false;
+        }
+        let value = field_value.trim().to_lowercase();
+        self.sentinels.iter().any(|s| s.trim().to_lowercase() == value)
+            || (self.standard_sentinels && STANDARD_SENTINELS.contains(&value.as_str()))
+    }
 }
 
 impl Transformer for NoneTransformer {
     fn transform(&self, field_value: &str, _: &str, _: usize) -> TransformResult {
-        if self.regex.is_match(field_value) {
+        let matches_regex = self.regex.as_ref().map(|r| r.is_match(field_value)).unwrap_or(false);
+        if matches_regex || self.is_sentinel(field_value) {
             TransformResult::excluded()
         } else {
             TransformResult::present(field_value)
@@ -0,0 +1,215 @@
+use Transformer;
+use transformer::{
+    TransformResultHelper,
+    TransformResult
+};
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// A Unicode normalization form.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum NormalForm {
+    /// Canonical composition, e.g. "e" followed by a combining acute accent to "é".
+    Nfc,
+    /// Compatibility composition, which also replaces compatibility characters like "ﬁ" and
+    /// "²" with their plain equivalents, "fi" and "2".
+    Nfkc,
+}
+
+/// Whether a character is invisible: a control character other than a tab or line break, or a
+/// zero-width, soft hyphen, byte order mark or bidirectional formatting character.
+fn is_invisible(c: char) -> bool {
+    match c {
+        '\t' | '\n' | '\r' => false,
+        '\u{00AD}' | '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2060}'..='\u{2064}' |
+        '\u{2066}'..='\u{2069}' | '\u{FEFF}' => true,
+        c => c.is_control(),
+    }
+}
+
+/// ASCII replacement for a typographic quote, dash or other punctuation character, if it is one.
+fn ascii_punctuation(c: char) -> Option<&'static str> {
+    match c {
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => Some("'"),
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' | '\u{00AB}' | '\u{00BB}' => Some("\""),
+        '\u{2010}'..='\u{2015}' | '\u{2212}' => Some("-"),
+        '\u{2026}' => Some("..."),
+        '\u{2022}' => Some("*"),
+        _ => None,
+    }
+}
+
+/// ASCII transliteration of a letter that doesn't decompose into an ASCII letter and combining
+/// marks, if it is one.
+fn transliterate_letter(c: char) -> Option<&'static str> {
+    match c {
+        'ß' => Some("ss"),
+        'æ' => Some("ae"),
+        'Æ' => Some("AE"),
+        'œ' => Some("oe"),
+        'Œ' => Some("OE"),
+        'ø' => Some("o"),
+        'Ø' => Some("O"),
+        'đ' | 'ð' => Some("d"),
+        'Đ' | 'Ð' => Some("D"),
+        'ł' => Some("l"),
+        'Ł' => Some("L"),
+        'þ' => Some("th"),
+        'Þ' => Some("Th"),
+        'ı' => Some("i"),
+        _ => None,
+    }
+}
+
+/// Whether a character is ASCII or a letter in one of the Latin blocks of Unicode.
+fn is_latin(c: char) -> bool {
+    matches!(c, '\u{0000}'..='\u{007F}' | '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}' |
+                '\u{2C60}'..='\u{2C7F}' | '\u{A720}'..='\u{A7FF}')
+}
+
+/// Transliterate accented Latin letters to ASCII, e.g. "Zoë Straße" to "Zoe Strasse", leaving
+/// characters without a transliteration, including letters of other scripts and their marks,
+/// unchanged.
+///
+/// ```
+/// use csv_sanity::transformers::transliterate;
+///
+/// assert_eq!(transliterate("Zoë Łukasz Straße"), "Zoe Lukasz Strasse");
+/// assert_eq!(transliterate("Café हिन्दी Йорк"), "Cafe हिन्दी Йорк");
+/// ```
+pub fn transliterate(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut strip_marks = false;
+    for c in value.nfd() {
+        if is_combining_mark(c) {
+            if !strip_marks {
+                result.push(c);
+            }
+            continue;
+        }
+        strip_marks = is_latin(c);
+        match transliterate_letter(c) {
+            Some(s) => result.push_str(s),
+            None => result.push(c),
+        }
+    }
+    // Recompose the marks kept on letters of other scripts, e.g. "й".
+    result.nfc().collect()
+}
+
+/// Normalize Unicode and clean up characters that commonly sneak into fields.
+///
+/// The steps are applied in the order: stripping invisible characters, Unicode normalization,
+/// replacing typographic punctuation, transliteration and collapsing whitespace.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformers::{
+///     NormalizeTransformer,
+///     NormalForm,
+/// };
+///
+/// let transformer = NormalizeTransformer::new().with_form(NormalForm::Nfc).stripping_invisible()
+///     .with_ascii_punctuation().collapsing_whitespace();
+/// assert_eq!(transformer.transform("\u{FEFF}Cafe\u{301}\u{200B}\u{A0} \u{201C}Le Flore\u{201D}", "Name", 1),
+///     Ok(Some("Café \"Le Flore\"".to_string())));
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct NormalizeTransformer {
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    form: Option<NormalForm>,
+    /// Replace runs of whitespace, including non-breaking spaces, with a single space and trim
+    /// whitespace from either end.
+    #[serde(default)]
+    collapse_whitespace: bool,
+    /// Replace typographic quotes, dashes and ellipses with their ASCII equivalents.
+    #[serde(default)]
+    ascii_punctuation: bool,
+    /// Strip control characters other than tabs and line breaks, and zero-width characters like
+    /// byte order marks.
+    #[serde(default)]
+    strip_invisible: bool,
+    /// Transliterate accented Latin letters to ASCII. See `transliterate`.
+    #[serde(default)]
+    transliterate: bool,
+}
+
+impl NormalizeTransformer {
+    pub fn new() -> NormalizeTransformer {
+        NormalizeTransformer {
+            form: None,
+            collapse_whitespace: false,
+            ascii_punctuation: false,
+            strip_invisible: false,
+            transliterate: false,
+        }
+    }
+
+    pub fn with_form(self, form: NormalForm) -> NormalizeTransformer {
+        NormalizeTransformer {
+            form: Some(form),
+            .. self
+        }
+    }
+
+    pub fn collapsing_whitespace(self) -> NormalizeTransformer {
+        NormalizeTransformer {
+            collapse_whitespace: true,
+            .. self
+        }
+    }
+
+    pub fn with_ascii_punctuation(self) -> NormalizeTransformer {
+        NormalizeTransformer {
+            ascii_punctuation: true,
+            .. self
+        }
+    }
+
+    pub fn stripping_invisible(self) -> NormalizeTransformer {
+        NormalizeTransformer {
+            strip_invisible: true,
+            .. self
+        }
+    }
+
+    pub fn transliterating(self) -> NormalizeTransformer {
+        NormalizeTransformer {
+            transliterate: true,
+            .. self
+        }
+    }
+}
+
+impl Transformer for NormalizeTransformer {
+    fn transform(&self, field_value: &str, _: &str, _: usize) -> TransformResult {
+        let mut value = if self.strip_invisible {
+            field_value.chars().filter(|&c| !is_invisible(c)).collect()
+        } else {
+            field_value.to_string()
+        };
+        value = match self.form {
+            Some(NormalForm::Nfc) => value.nfc().collect(),
+            Some(NormalForm::Nfkc) => value.nfkc().collect(),
+            None => value,
+        };
+        if self.ascii_punctuation {
+            let mut replaced = String::with_capacity(value.len());
+            for c in value.chars() {
+                match ascii_punctuation(c) {
+                    Some(s) => replaced.push_str(s),
+                    None => replaced.push(c),
+                }
+            }
+            value = replaced;
+        }
+        if self.transliterate {
+            value = transliterate(&value);
+        }
+        if self.collapse_whitespace {
+            value = value.split_whitespace().collect::<Vec<&str>>().join(" ");
+        }
+        TransformResult::present(&value)
+    }
+}
@@ -5,26 +5,420 @@ use transformer::{
 };
 
 use regex::Regex;
+use serde::{
+    Serialize,
+    Serializer,
+    Deserialize,
+    Deserializer,
+};
+use std::cmp::Ordering;
+use std::fmt::{
+    self,
+    Formatter,
+    Display,
+};
+use std::str::FromStr;
 
 lazy_static! {
-    static ref INTEGER_REGEX: Regex = Regex::new(r"\A(:?0|[1-9]\d*)\z").unwrap();
+    static ref INTEGER_REGEX: Regex = Regex::new(r"\A(?:0|[1-9]\d*)\z").unwrap();
+    static ref DECIMAL_REGEX: Regex = Regex::new(r"\A(?:0|[1-9]\d*)(?:\.\d+)?\z").unwrap();
+}
+
+/// An exact decimal number, stored as its digits to avoid floating-point rounding errors.
+///
+/// `Decimal`s parse from and display as their canonical representation: an optional minus sign,
+/// the integer digits without leading zeros and, if the number has a fractional part, a `.`
+/// followed by the fractional digits without trailing zeros.
+///
+/// ```
+/// use csv_sanity::transformers::Decimal;
+///
+/// let n: Decimal = "-0012.3400".parse().unwrap();
+/// assert_eq!(n.to_string(), "-12.34");
+/// assert_eq!(n.round(1).to_string(), "-12.3");
+/// assert_eq!(n.to_fixed(3), "-12.340");
+/// assert!(n < "-12".parse().unwrap());
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Decimal {
+    negative: bool,
+    integer: String,
+    fraction: String,
+}
+
+impl Decimal {
+    /// Construct a `Decimal` from its sign and ASCII digits, normalizing away leading and trailing
+    /// zeros.
+    fn from_parts(negative: bool, integer: &str, fraction: &str) -> Decimal {
+        let integer = integer.trim_start_matches('0');
+        let fraction = fraction.trim_end_matches('0');
+        let is_zero = integer.is_empty() && fraction.is_empty();
+        Decimal {
+            negative: negative && !is_zero,
+            integer: if integer.is_empty() { "0".to_string() } else { integer.to_string() },
+            fraction: fraction.to_string(),
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Number of digits after the decimal point.
+    pub fn decimal_places(&self) -> usize {
+        self.fraction.len()
+    }
+
+    /// Round to a number of decimal places, rounding halves away from zero.
+    pub fn round(&self, decimal_places: usize) -> Decimal {
+        if self.fraction.len() <= decimal_places {
+            return self.clone();
+        }
+        let mut digits: Vec<u8> = self.integer.bytes()
+            .chain(self.fraction.bytes().take(decimal_places))
+            .map(|b| b - b'0')
+            .collect();
+        if self.fraction.as_bytes()[decimal_places] >= b'5' {
+            // Carry the rounding up through the digits, adding a new leading digit if needed.
+            let mut i = digits.len();
+            loop {
+                if i == 0 {
+                    digits.insert(0, 1);
+                    break;
+                }
+                i -= 1;
+                if digits[i] == 9 {
+                    digits[i] = 0;
+                } else {
+                    digits[i] += 1;
+                    break;
+                }
+            }
+        }
+        let digits: String = digits.iter().map(|d| (d + b'0') as char).collect();
+        let split = digits.len() - decimal_places;
+        Decimal::from_parts(self.negative, &digits[..split], &digits[split..])
+    }
+
+    /// Format with exactly the specified number of decimal places, rounding if necessary.
+    pub fn to_fixed(&self, decimal_places: usize) -> String {
+        let rounded = self.round(decimal_places);
+        let mut s = String::new();
+        if rounded.negative {
+            s.push('-');
+        }
+        s.push_str(&rounded.integer);
+        if decimal_places > 0 {
+            s.push('.');
+            s.push_str(&rounded.fraction);
+            for _ in rounded.fraction.len()..decimal_places {
+                s.push('0');
+            }
+        }
+        s
+    }
+
+    fn cmp_magnitude(&self, other: &Decimal) -> Ordering {
+        self.integer.len().cmp(&other.integer.len())
+            .then_with(|| self.integer.cmp(&other.integer))
+            .then_with(|| self.fraction.cmp(&other.fraction))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.cmp_magnitude(other),
+            (true, true) => other.cmp_magnitude(self),
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let decimal_places = self.fraction.len();
+        write!(formatter, "{}", self.to_fixed(decimal_places))
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Decimal, String> {
+        parse_decimal(s, '.', None).map_err(|reason| format!("{}: {:?}", reason, s))
+    }
+}
+
+impl Serialize for Decimal
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+      where S: Serializer {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal
+{
+    fn deserialize<D>(deserializer: D) -> Result<Decimal, D::Error>
+      where D: Deserializer<'de>
+    {
+        use serde::de::Error;
+        let string: String = Deserialize::deserialize(deserializer)?;
+        string.parse().map_err(D::Error::custom)
+    }
+}
+
+/// Parse a signed decimal number with the specified decimal separator and optional thousands
+/// separator.
+///
+/// If a thousands separator is given, digits before the decimal separator may be grouped by it in
+/// groups of three.
+pub fn parse_decimal(value: &str, decimal_separator: char, thousands_separator: Option<char>) -> Result<Decimal, &'static str> {
+    let (negative, unsigned) = if value.starts_with('-') {
+        (true, &value[1..])
+    } else if value.starts_with('+') {
+        (false, &value[1..])
+    } else {
+        (false, value)
+    };
+
+    let mut parts = unsigned.splitn(2, decimal_separator);
+    let integer_part = parts.next().unwrap_or("");
+    let fraction = parts.next().unwrap_or("");
+    let integer: String = match thousands_separator {
+        Some(separator) if integer_part.contains(separator) => {
+            let groups: Vec<&str> = integer_part.split(separator).collect();
+            let valid_grouping = groups.iter().enumerate().all(|(i, group)| {
+                if i == 0 { group.len() >= 1 && group.len() <= 3 } else { group.len() == 3 }
+            });
+            if !valid_grouping {
+                return Err("invalid digit grouping");
+            }
+            groups.concat()
+        },
+        _ => integer_part.to_string(),
+    };
+
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !is_digits(&integer) || !is_digits(fraction) {
+        return Err("not a valid number");
+    }
+    Ok(Decimal::from_parts(negative, &integer, fraction))
 }
 
+/// Whether a number must be a whole integer or may have a fractional part.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum NumberKind {
+    Integer,
+    Decimal,
+}
+
+impl Default for NumberKind {
+    fn default() -> NumberKind {
+        NumberKind::Integer
+    }
+}
+
+fn default_decimal_separator() -> char {
+    '.'
+}
+
+/// Parse fields as numbers, optionally rewriting them in their canonical form, e.g. "1,234.50" as
+/// "1234.5".
+///
+/// Unless canonicalizing, fields must be plain unsigned numbers without leading zeros, e.g. "0",
+/// "42" or "3.25", and are left unchanged, so that ID-like fields such as "007" aren't rewritten.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformers::NumberTransformer;
+///
+/// let transformer = NumberTransformer::european_style().with_parentheses_negative();
+/// assert_eq!(transformer.transform("(1.234,50 €)", "Balance", 1), Ok(Some("-1234.5".to_string())));
+///
+/// let transformer = NumberTransformer::match_integer();
+/// assert_eq!(transformer.transform("42", "Id", 1), Ok(Some("42".to_string())));
+/// assert!(transformer.transform("007", "Id", 1).is_err());
+/// assert_eq!(transformer.canonicalizing().transform(" 007", "Id", 1), Ok(Some("7".to_string())));
+/// ```
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
-pub struct NumberTransformer { }
+pub struct NumberTransformer {
+    #[serde(default)]
+    kind: NumberKind,
+    #[serde(default = "default_decimal_separator")]
+    decimal_separator: char,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    thousands_separator: Option<char>,
+    #[serde(default)]
+    allow_currency_symbols: bool,
+    #[serde(default)]
+    parentheses_negative: bool,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    min: Option<Decimal>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    max: Option<Decimal>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    max_decimal_places: Option<usize>,
+    /// Accept surrounding whitespace, signs, leading zeros and the configured separators and
+    /// symbols, rewriting numbers in their canonical form.
+    #[serde(default)]
+    canonicalize: bool,
+}
 
 impl NumberTransformer {
     pub fn match_integer() -> NumberTransformer {
-        NumberTransformer { }
+        NumberTransformer {
+            kind: NumberKind::Integer,
+            decimal_separator: default_decimal_separator(),
+            thousands_separator: None,
+            allow_currency_symbols: false,
+            parentheses_negative: false,
+            min: None,
+            max: None,
+            max_decimal_places: None,
+            canonicalize: false,
+        }
+    }
+
+    /// Match decimal numbers formatted like "1,234.50", allowing currency symbols, and rewrite
+    /// them in their canonical form.
+    pub fn us_style() -> NumberTransformer {
+        NumberTransformer {
+            kind: NumberKind::Decimal,
+            thousands_separator: Some(','),
+            allow_currency_symbols: true,
+            canonicalize: true,
+            .. Self::match_integer()
+        }
+    }
+
+    /// Match decimal numbers formatted like "1.234,50", allowing currency symbols, and rewrite
+    /// them in their canonical form.
+    pub fn european_style() -> NumberTransformer {
+        NumberTransformer {
+            decimal_separator: ',',
+            thousands_separator: Some('.'),
+            .. Self::us_style()
+        }
+    }
+
+    /// Accept surrounding whitespace, signs and leading zeros, e.g. " -007", and rewrite numbers in
+    /// their canonical form, e.g. "-7".
+    pub fn canonicalizing(self) -> NumberTransformer {
+        NumberTransformer {
+            canonicalize: true,
+            .. self
+        }
+    }
+
+    /// Interpret numbers in parentheses, e.g. "(100)", as negative.
+    pub fn with_parentheses_negative(self) -> NumberTransformer {
+        NumberTransformer {
+            parentheses_negative: true,
+            .. self
+        }
+    }
+
+    /// Reject numbers outside of the specified inclusive bounds.
+    pub fn with_bounds(self, min: Option<Decimal>, max: Option<Decimal>) -> NumberTransformer {
+        NumberTransformer {
+            min: min,
+            max: max,
+            .. self
+        }
+    }
+
+    /// Reject numbers with more than the specified number of decimal places.
+    pub fn with_max_decimal_places(self, max_decimal_places: usize) -> NumberTransformer {
+        NumberTransformer {
+            max_decimal_places: Some(max_decimal_places),
+            .. self
+        }
+    }
+
+    /// Parse a field as a number according to this transformer's format, without checking bounds.
+    pub fn parse(&self, field_value: &str) -> Result<Decimal, &'static str> {
+        let mut value = field_value.trim();
+        let mut negative = false;
+        if self.parentheses_negative && value.starts_with('(') && value.ends_with(')') {
+            negative = true;
+            value = value[1..value.len() - 1].trim();
+        }
+        let value = if self.allow_currency_symbols {
+            strip_currency_symbols(value)
+        } else {
+            value.to_string()
+        };
+        let mut number = parse_decimal(&value, self.decimal_separator, self.thousands_separator)?;
+        if negative {
+            if number.negative {
+                return Err("not a valid number");
+            }
+            number = Decimal::from_parts(true, &number.integer, &number.fraction);
+        }
+        Ok(number)
     }
 }
 
+/// Strip currency symbols and the whitespace around them from either end of a value, keeping a
+/// sign that precedes the symbol, e.g. "-$5" or "5 €".
+pub fn strip_currency_symbols(value: &str) -> String {
+    let is_symbol = |c: char| "$€£¥₹₩₽₺₪₫₱฿¢".contains(c);
+    let value = value.trim_end_matches(is_symbol).trim_end();
+    let (sign, rest) = if value.starts_with('-') || value.starts_with('+') {
+        value.split_at(1)
+    } else {
+        ("", value)
+    };
+    format!("{}{}", sign, rest.trim_start_matches(is_symbol).trim_start())
+}
+
 impl Transformer for NumberTransformer {
     fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
-        if INTEGER_REGEX.is_match(field_value) {
-            TransformResult::present(field_value)
+        if !self.canonicalize {
+            let regex = match self.kind {
+                NumberKind::Integer => &*INTEGER_REGEX,
+                NumberKind::Decimal => &*DECIMAL_REGEX,
+            };
+            if !regex.is_match(field_value) {
+                return TransformResult::error(field_value, field_name, record_n, "not a valid number");
+            }
+        }
+        let number = match self.parse(field_value) {
+            Ok(n) => n,
+            Err(reason) => return TransformResult::error(field_value, field_name, record_n, reason),
+        };
+        if self.kind == NumberKind::Integer && number.decimal_places() > 0 {
+            return TransformResult::error(field_value, field_name, record_n, "not a valid integer");
+        }
+        if let Some(max_decimal_places) = self.max_decimal_places {
+            if number.decimal_places() > max_decimal_places {
+                return TransformResult::error(field_value, field_name, record_n,
+                    &format!("more than {} decimal places", max_decimal_places));
+            }
+        }
+        if let Some(ref min) = self.min {
+            if &number < min {
+                return TransformResult::error(field_value, field_name, record_n, &format!("less than minimum {}", min));
+            }
+        }
+        if let Some(ref max) = self.max {
+            if &number > max {
+                return TransformResult::error(field_value, field_name, record_n, &format!("greater than maximum {}", max));
+            }
+        }
+        if self.canonicalize {
+            TransformResult::present(&number.to_string())
         } else {
-            TransformResult::error(field_value, field_name, record_n, "not a valid number")
+            TransformResult::present(field_value)
         }
     }
 }
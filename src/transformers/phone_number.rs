@@ -4,31 +4,255 @@ use transformer::{
     TransformResult
 };
 
+use phonenumber::{
+    self,
+    Mode,
+    ParseError,
+};
+use phonenumber::country::Id;
 use regex::Regex;
 
 lazy_static! {
     static ref NANP_REGEX: Regex = Regex::new(r"\A(?:\+?1)?\D*\(?(?P<area>\d{3})\)?\D*(?P<exchange>\d{3})\D*(?P<subscriber>\d{4})\z").unwrap();
+    static ref EXTENSION_REGEX: Regex = Regex::new(r"(?i)\A(?P<number>.*?)\s*(?:x|ext\.?|extension)\s*(?P<extension>\d+)\z").unwrap();
+}
+
+/// A region whose numbering plan is used for phone numbers without a country code, as an ISO
+/// 3166-1 alpha-2 code, e.g. `"GB".parse::<Region>()`.
+pub type Region = Id;
+
+/// How to format phone numbers.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum PhoneNumberFormat {
+    /// Only accept North American Numbering Plan numbers, formatted like "+1 212 555 0123".
+    Nanp,
+    /// E.164, e.g. "+442079460958".
+    E164,
+    /// International format with the region's grouping, e.g. "+44 20 7946 0958".
+    International,
+    /// National format with the region's grouping and trunk prefix, e.g. "020 7946 0958".
+    National,
+}
+
+impl Default for PhoneNumberFormat {
+    fn default() -> PhoneNumberFormat {
+        PhoneNumberFormat::Nanp
+    }
+}
+
+/// What to do with phone number extensions, e.g. "x123".
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Extensions {
+    Keep,
+    Drop,
+    Reject,
+}
+
+impl Default for Extensions {
+    fn default() -> Extensions {
+        Extensions::Keep
+    }
 }
 
+/// Validate and format phone numbers.
+///
+/// Other than in the NANP format, numbers are validated against per-country length and prefix
+/// metadata bundled with csv-sanity. Numbers without a country code are parsed according to the
+/// numbering plan of the default region, and are rejected if there isn't one.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformers::{
+///     PhoneNumberTransformer,
+///     PhoneNumberFormat,
+/// };
+///
+/// let transformer = PhoneNumberTransformer::international(Some("GB".parse().unwrap()), PhoneNumberFormat::E164);
+/// assert_eq!(transformer.transform("020 7946 0958 x12", "Phone", 1), Ok(Some("+442079460958;ext=12".to_string())));
+/// assert_eq!(transformer.transform("+49 30 901820", "Phone", 1), Ok(Some("+4930901820".to_string())));
+/// ```
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
-pub struct PhoneNumberTransformer { }
+pub struct PhoneNumberTransformer {
+    #[serde(default)]
+    format: PhoneNumberFormat,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    default_region: Option<Region>,
+    #[serde(default)]
+    extensions: Extensions,
+    /// Reject NANP numbers from 555-0100 to 555-0199, which are reserved for fictional use.
+    #[serde(default)]
+    reject_555: bool,
+    /// Reject NANP numbers that aren't valid according to the bundled metadata, e.g. because the
+    /// area code isn't in use.
+    #[serde(default)]
+    validate_area_codes: bool,
+}
 
 impl PhoneNumberTransformer {
     pub fn expect_nanp_format() -> PhoneNumberTransformer {
-        PhoneNumberTransformer { }
+        PhoneNumberTransformer {
+            format: PhoneNumberFormat::Nanp,
+            default_region: None,
+            extensions: Default::default(),
+            reject_555: false,
+            validate_area_codes: false,
+        }
+    }
+
+    /// Accept phone numbers from any country, formatting them in the specified format.
+    pub fn international(default_region: Option<Region>, format: PhoneNumberFormat) -> PhoneNumberTransformer {
+        PhoneNumberTransformer {
+            format: format,
+            default_region: default_region,
+            extensions: Default::default(),
+            reject_555: false,
+            validate_area_codes: false,
+        }
+    }
+
+    /// Set what to do with phone number extensions. See `Extensions`.
+    pub fn extensions(self, extensions: Extensions) -> PhoneNumberTransformer {
+        PhoneNumberTransformer {
+            extensions: extensions,
+            .. self
+        }
+    }
+
+    /// Reject NANP numbers from 555-0100 to 555-0199, which are reserved for fictional use. Other
+    /// 555 numbers, like 555-1212 for directory assistance, are accepted.
+    ///
+    /// ```
+    /// use csv_sanity::Transformer;
+    /// use csv_sanity::transformers::PhoneNumberTransformer;
+    ///
+    /// let transformer = PhoneNumberTransformer::expect_nanp_format().reject_555().validate_area_codes();
+    /// assert_eq!(transformer.transform("(212) 555-0123", "Phone", 1).unwrap_err().reason,
+    ///     "555 numbers are fictional");
+    /// assert_eq!(transformer.transform("(212) 555-1212", "Phone", 1), Ok(Some("+1 212 555 1212".to_string())));
+    /// assert_eq!(transformer.transform("555 234 4567", "Phone", 1).unwrap_err().reason,
+    ///     "area code 555 is not assigned");
+    /// assert!(transformer.transform("555 123 4567", "Phone", 1).is_err());
+    /// assert_eq!(transformer.transform("212-911-0123", "Phone", 1).unwrap_err().reason,
+    ///     "exchange code must not be an N11 service code");
+    /// assert_eq!(transformer.transform("999-234-0123", "Phone", 1).unwrap_err().reason,
+    ///     "not a valid NANP phone number");
+    /// ```
+    pub fn reject_555(self) -> PhoneNumberTransformer {
+        PhoneNumberTransformer {
+            reject_555: true,
+            .. self
+        }
+    }
+
+    /// Reject NANP numbers that aren't valid according to the bundled metadata, e.g. because the
+    /// area code isn't in use. The metadata validates the number as a whole, so the error doesn't
+    /// say which part of it is invalid.
+    pub fn validate_area_codes(self) -> PhoneNumberTransformer {
+        PhoneNumberTransformer {
+            validate_area_codes: true,
+            .. self
+        }
+    }
+
+    fn transform_nanp(&self, value: &str) -> Result<String, String> {
+        let (number, extension) = match EXTENSION_REGEX.captures(value) {
+            Some(captures) => (captures.name("number").unwrap().as_str(), captures.name("extension").map(|e| e.as_str())),
+            None => (value, None),
+        };
+        let captures = match NANP_REGEX.captures(number) {
+            Some(c) => c,
+            None => return Err("not a valid NANP format phone number".to_string()),
+        };
+        let area_code = captures.name("area").unwrap().as_str();
+        let exchange_code = captures.name("exchange").unwrap().as_str();
+        let subscriber_number = captures.name("subscriber").unwrap().as_str();
+        check_nanp_code(area_code, "area code")?;
+        check_nanp_code(exchange_code, "exchange code")?;
+        if area_code == "555" {
+            return Err("area code 555 is not assigned".to_string());
+        }
+        let is_fictional = exchange_code == "555" && subscriber_number.starts_with("01");
+        if self.reject_555 && is_fictional {
+            return Err("555 numbers are fictional".to_string());
+        }
+        if self.validate_area_codes {
+            let number = format!("+1{}{}{}", area_code, exchange_code, subscriber_number);
+            if !phonenumber::parse(None, number).map(|n| phonenumber::is_valid(&n)).unwrap_or(false) {
+                return Err("not a valid NANP phone number".to_string());
+            }
+        }
+        let phone_number = format!("+1 {} {} {}", area_code, exchange_code, subscriber_number);
+        match (extension, self.extensions) {
+            (Some(_), Extensions::Reject) => Err("phone number has an extension".to_string()),
+            (Some(extension), Extensions::Keep) => Ok(format!("{} x{}", phone_number, extension)),
+            _ => Ok(phone_number),
+        }
+    }
+
+    fn transform_international(&self, value: &str) -> Result<String, String> {
+        let mut number = phonenumber::parse(self.default_region, value).map_err(|e| match e {
+            ParseError::InvalidCountryCode if self.default_region.is_none() && !value.trim().starts_with('+') => {
+                "no country code".to_string()
+            },
+            ParseError::InvalidCountryCode => "invalid country code".to_string(),
+            ParseError::TooShortAfterIdd | ParseError::TooShortNsn => "too short to be a phone number".to_string(),
+            ParseError::TooLong => "too long to be a phone number".to_string(),
+            _ => "not a valid phone number".to_string(),
+        })?;
+        if !phonenumber::is_valid(&number) {
+            return Err(match number.country().id() {
+                Some(id) => format!("not a valid {} phone number", id.as_ref()),
+                None => "not a valid phone number".to_string(),
+            });
+        }
+
+        let extension = number.extension().map(|e| e.to_string());
+        if extension.is_some() {
+            match self.extensions {
+                Extensions::Keep => {},
+                Extensions::Drop => {
+                    // Reparse without the extension so that it's left out of the formatted number.
+                    let without_extension = format!("+{}{}", number.country().code(), number.national());
+                    number = phonenumber::parse(None, without_extension).map_err(|e| e.to_string())?;
+                },
+                Extensions::Reject => return Err("phone number has an extension".to_string()),
+            }
+        }
+        Ok(match self.format {
+            PhoneNumberFormat::E164 => {
+                let formatted = number.format().mode(Mode::E164).to_string();
+                match (extension, self.extensions) {
+                    (Some(ref extension), Extensions::Keep) => format!("{};ext={}", formatted, extension),
+                    _ => formatted,
+                }
+            },
+            PhoneNumberFormat::National => number.format().mode(Mode::National).to_string(),
+            PhoneNumberFormat::International | PhoneNumberFormat::Nanp => number.format().mode(Mode::International).to_string(),
+        })
+    }
+}
+
+/// Check that a NANP area or exchange code doesn't start with 0 or 1 and isn't an N11 service
+/// code, e.g. 911.
+fn check_nanp_code(code: &str, name: &str) -> Result<(), String> {
+    if code.starts_with('0') || code.starts_with('1') {
+        Err(format!("{} must not start with 0 or 1", name))
+    } else if &code[1..] == "11" {
+        Err(format!("{} must not be an N11 service code", name))
+    } else {
+        Ok(())
     }
 }
 
 impl Transformer for PhoneNumberTransformer {
     fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
-        if let Some(captures) = NANP_REGEX.captures(field_value) {
-            let area_code = captures.name("area").unwrap().as_str();
-            let exchange_code = captures.name("exchange").unwrap().as_str();
-            let subscriber_number = captures.name("subscriber").unwrap().as_str();
-            let phone_number = format!("+1 {} {} {}", area_code, exchange_code, subscriber_number);
-            TransformResult::present(&phone_number)
-        } else {
-            TransformResult::error(field_value, field_name, record_n, "not a valid NANP format phone number")
+        let result = match self.format {
+            PhoneNumberFormat::Nanp => self.transform_nanp(field_value),
+            _ => self.transform_international(field_value),
+        };
+        match result {
+            Ok(number) => TransformResult::present(&number),
+            Err(reason) => TransformResult::error(field_value, field_name, record_n, &reason),
         }
     }
 }
@@ -0,0 +1,170 @@
+use Transformer;
+use transformer::{
+    TransformResultHelper,
+    TransformResult,
+    InputRecord,
+};
+use super::map::Unmapped;
+use super::country::country_code;
+
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Postal code formats by ISO 3166-1 alpha-2 country code: patterns matched against the
+/// uppercased postal code with runs of whitespace collapsed, each with a replacement template for
+/// the canonical form, and the lengths that all-digit postal codes may be zero-padded to.
+static FORMATS: &'static [(&'static str, &'static [(&'static str, &'static str)], &'static [usize])] = &[
+    ("US", &[(r"\A(\d{5})\z", "$1"), (r"\A(\d{5}) ?-? ?(\d{4})\z", "$1-$2")], &[5, 9]),
+    ("CA", &[(r"\A([ABCEGHJ-NPRSTVXY]\d[ABCEGHJ-NPRSTV-Z]) ?(\d[ABCEGHJ-NPRSTV-Z]\d)\z", "$1 $2")], &[]),
+    ("GB", &[(r"\A([A-Z]{1,2}\d[A-Z\d]?) ?(\d[A-Z]{2})\z", "$1 $2"), (r"\A(GIR) ?(0AA)\z", "$1 $2")], &[]),
+    ("IE", &[(r"\A([AC-FHKNPRTV-Y]\d{2}|D6W) ?([0-9AC-FHKNPRTV-Y]{4})\z", "$1 $2")], &[]),
+    ("DE", &[(r"\A(\d{5})\z", "$1")], &[5]),
+    ("FR", &[(r"\A(\d{5})\z", "$1")], &[5]),
+    ("IT", &[(r"\A(\d{5})\z", "$1")], &[5]),
+    ("ES", &[(r"\A(\d{5})\z", "$1")], &[5]),
+    ("MX", &[(r"\A(\d{5})\z", "$1")], &[5]),
+    ("NL", &[(r"\A([1-9]\d{3}) ?([A-Z]{2})\z", "$1 $2")], &[]),
+    ("BE", &[(r"\A([1-9]\d{3})\z", "$1")], &[]),
+    ("AT", &[(r"\A([1-9]\d{3})\z", "$1")], &[]),
+    ("CH", &[(r"\A([1-9]\d{3})\z", "$1")], &[]),
+    ("DK", &[(r"\A(\d{4})\z", "$1")], &[4]),
+    ("NO", &[(r"\A(\d{4})\z", "$1")], &[4]),
+    ("AU", &[(r"\A(\d{4})\z", "$1")], &[4]),
+    ("SE", &[(r"\A(\d{3}) ?(\d{2})\z", "$1 $2")], &[]),
+    ("PL", &[(r"\A(\d{2})-?(\d{3})\z", "$1-$2")], &[5]),
+    ("BR", &[(r"\A(\d{5})-?(\d{3})\z", "$1-$2")], &[8]),
+    ("JP", &[(r"\A(\d{3})-?(\d{4})\z", "$1-$2")], &[7]),
+    ("IN", &[(r"\A([1-9]\d{2}) ?(\d{3})\z", "$1$2")], &[]),
+];
+
+lazy_static! {
+    static ref PATTERNS: HashMap<&'static str, Vec<(Regex, &'static str)>> = FORMATS.iter()
+        .map(|&(country, patterns, _)| {
+            (country, patterns.iter().map(|&(p, template)| (Regex::new(p).unwrap(), template)).collect())
+        })
+        .collect();
+}
+
+/// Validate postal codes against the format of their country, rewriting them in its canonical
+/// form, e.g. "sw1a1aa" as "SW1A 1AA" for GB.
+///
+/// The country is read from the `country_field` of the record if it's set and not empty, and is
+/// otherwise the static `country`. Countries may be given as ISO 3166-1 codes or as names, as
+/// accepted by `CountryTransformer`.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformer::InputRecord;
+/// use csv_sanity::transformers::PostalCodeTransformer;
+///
+/// let transformer = PostalCodeTransformer::for_country("US").with_country_field("Country").restoring_leading_zeros();
+/// let headers = vec!["Country".to_string(), "Postal Code".to_string()];
+/// let fields = vec!["Canada".to_string(), "k1a0b1".to_string()];
+/// let record = InputRecord::new(&headers, &fields);
+/// assert_eq!(transformer.transform_in_record("k1a0b1", "Postal Code", &record, 1), Ok(Some("K1A 0B1".to_string())));
+/// assert_eq!(transformer.transform("2134", "Postal Code", 1), Ok(Some("02134".to_string())));
+/// assert_eq!(transformer.transform("02134 apt 4", "Postal Code", 1).unwrap_err().reason,
+///     "not a valid US postal code");
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct PostalCodeTransformer {
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    country: Option<String>,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    country_field: Option<String>,
+    /// Restore leading zeros dropped by spreadsheet software, e.g. "2134" to "02134" for US.
+    #[serde(default)]
+    restore_leading_zeros: bool,
+    /// What to do with postal codes from countries without a known format.
+    #[serde(default)]
+    unknown_country: Unmapped,
+}
+
+impl PostalCodeTransformer {
+    /// Construct a `PostalCodeTransformer` for postal codes from a single country.
+    pub fn for_country(country: &str) -> PostalCodeTransformer {
+        PostalCodeTransformer {
+            country: Some(country.to_string()),
+            country_field: None,
+            restore_leading_zeros: false,
+            unknown_country: Default::default(),
+        }
+    }
+
+    /// Read the country of each postal code from the field with the specified name, falling back
+    /// to the static country if that field is empty.
+    pub fn with_country_field(self, country_field: &str) -> PostalCodeTransformer {
+        PostalCodeTransformer {
+            country_field: Some(country_field.to_string()),
+            .. self
+        }
+    }
+
+    pub fn restoring_leading_zeros(self) -> PostalCodeTransformer {
+        PostalCodeTransformer {
+            restore_leading_zeros: true,
+            .. self
+        }
+    }
+
+    /// Set what to do with postal codes from countries without a known format. See `Unmapped`.
+    pub fn unknown_country(self, unknown_country: Unmapped) -> PostalCodeTransformer {
+        PostalCodeTransformer {
+            unknown_country: unknown_country,
+            .. self
+        }
+    }
+
+    /// Name of the field the country of each postal code is read from, if any.
+    pub fn country_field(&self) -> Option<&str> {
+        self.country_field.as_ref().map(|s| s.as_str())
+    }
+
+    fn transform_for_country(&self, field_value: &str, field_name: &str, country: Option<&str>, record_n: usize) -> TransformResult {
+        let country = match country {
+            Some(c) => country_code(c).map(|code| code.to_string()).unwrap_or_else(|| c.trim().to_uppercase()),
+            None => return TransformResult::error(field_value, field_name, record_n, "no country for postal code"),
+        };
+        let (patterns, pad_lengths) = match FORMATS.iter().find(|&&(c, _, _)| c == country) {
+            Some(&(c, _, pad_lengths)) => (&PATTERNS[c], pad_lengths),
+            None => return match self.unknown_country {
+                Unmapped::PassThrough => TransformResult::present(field_value),
+                Unmapped::Null => TransformResult::excluded(),
+                Unmapped::Error => TransformResult::error(field_value, field_name, record_n,
+                    &format!("no postal code format for country {}", country)),
+            },
+        };
+
+        let mut value = field_value.split_whitespace().collect::<Vec<&str>>().join(" ").to_uppercase();
+        if self.restore_leading_zeros && value.chars().all(|c| c.is_ascii_digit()) {
+            // Pad by at most two zeros, since spreadsheets only drop the zeros before the first
+            // non-zero digit.
+            if let Some(&length) = pad_lengths.iter().find(|&&l| l >= value.len() && l - value.len() <= 2) {
+                value = format!("{:0>width$}", value, width = length);
+            }
+        }
+        for &(ref regex, template) in patterns.iter() {
+            if let Some(captures) = regex.captures(&value) {
+                let mut postal_code = String::new();
+                captures.expand(template, &mut postal_code);
+                return TransformResult::present(&postal_code);
+            }
+        }
+        TransformResult::error(field_value, field_name, record_n, &format!("not a valid {} postal code", country))
+    }
+}
+
+impl Transformer for PostalCodeTransformer {
+    fn transform_in_record(&self, field_value: &str, field_name: &str, record: &InputRecord, record_n: usize) -> TransformResult {
+        let country = self.country_field.as_ref()
+            .and_then(|f| record.get(f))
+            .filter(|c| !c.trim().is_empty())
+            .or(self.country.as_ref().map(|s| s.as_str()));
+        self.transform_for_country(field_value, field_name, country, record_n)
+    }
+
+    fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
+        let country = self.country.as_ref().map(|s| s.as_str());
+        self.transform_for_country(field_value, field_name, country, record_n)
+    }
+}
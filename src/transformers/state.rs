@@ -0,0 +1,175 @@
+use Transformer;
+use transformer::{
+    TransformResultHelper,
+    TransformResult
+};
+use super::country::name_key;
+
+use std::collections::HashMap;
+
+/// USPS state codes, names, AP style abbreviations and other common names of the states and
+/// the District of Columbia.
+static STATES: &'static [(&'static str, &'static str, &'static [&'static str])] = &[
+    ("AL", "Alabama", &["Ala."]),
+    ("AK", "Alaska", &[]),
+    ("AZ", "Arizona", &["Ariz."]),
+    ("AR", "Arkansas", &["Ark."]),
+    ("CA", "California", &["Calif.", "Cal."]),
+    ("CO", "Colorado", &["Colo."]),
+    ("CT", "Connecticut", &["Conn."]),
+    ("DE", "Delaware", &["Del."]),
+    ("DC", "District of Columbia", &["Washington, D.C.", "Washington DC", "Wash. D.C."]),
+    ("FL", "Florida", &["Fla."]),
+    ("GA", "Georgia", &[]),
+    ("HI", "Hawaii", &["Hawai'i"]),
+    ("ID", "Idaho", &[]),
+    ("IL", "Illinois", &["Ill."]),
+    ("IN", "Indiana", &["Ind."]),
+    ("IA", "Iowa", &[]),
+    ("KS", "Kansas", &["Kan.", "Kans."]),
+    ("KY", "Kentucky", &[]),
+    ("LA", "Louisiana", &[]),
+    ("ME", "Maine", &[]),
+    ("MD", "Maryland", &[]),
+    ("MA", "Massachusetts", &["Mass."]),
+    ("MI", "Michigan", &["Mich."]),
+    ("MN", "Minnesota", &["Minn."]),
+    ("MS", "Mississippi", &["Miss."]),
+    ("MO", "Missouri", &[]),
+    ("MT", "Montana", &["Mont."]),
+    ("NE", "Nebraska", &["Neb.", "Nebr."]),
+    ("NV", "Nevada", &["Nev."]),
+    ("NH", "New Hampshire", &["N.H."]),
+    ("NJ", "New Jersey", &["N.J."]),
+    ("NM", "New Mexico", &["N.M.", "N. Mex."]),
+    ("NY", "New York", &["N.Y."]),
+    ("NC", "North Carolina", &["N.C.", "N. Carolina"]),
+    ("ND", "North Dakota", &["N.D.", "N. Dakota", "N. Dak."]),
+    ("OH", "Ohio", &[]),
+    ("OK", "Oklahoma", &["Okla."]),
+    ("OR", "Oregon", &["Ore.", "Oreg."]),
+    ("PA", "Pennsylvania", &["Penn.", "Penna."]),
+    ("RI", "Rhode Island", &["R.I."]),
+    ("SC", "South Carolina", &["S.C.", "S. Carolina"]),
+    ("SD", "South Dakota", &["S.D.", "S. Dakota", "S. Dak."]),
+    ("TN", "Tennessee", &["Tenn."]),
+    ("TX", "Texas", &["Tex."]),
+    ("UT", "Utah", &[]),
+    ("VT", "Vermont", &[]),
+    ("VA", "Virginia", &[]),
+    ("WA", "Washington", &["Wash."]),
+    ("WV", "West Virginia", &["W. Va.", "W. Virginia"]),
+    ("WI", "Wisconsin", &["Wis.", "Wisc."]),
+    ("WY", "Wyoming", &["Wyo."]),
+];
+
+/// USPS codes and names of the territories, freely associated states and armed forces mail
+/// regions.
+static TERRITORIES: &'static [(&'static str, &'static str, &'static [&'static str])] = &[
+    ("AS", "American Samoa", &[]),
+    ("GU", "Guam", &[]),
+    ("MP", "Northern Mariana Islands", &[]),
+    ("PR", "Puerto Rico", &["P.R."]),
+    ("VI", "U.S. Virgin Islands", &["Virgin Islands"]),
+    ("FM", "Federated States of Micronesia", &["Micronesia"]),
+    ("MH", "Marshall Islands", &[]),
+    ("PW", "Palau", &[]),
+    ("AA", "Armed Forces Americas", &[]),
+    ("AE", "Armed Forces Europe", &[]),
+    ("AP", "Armed Forces Pacific", &[]),
+];
+
+lazy_static! {
+    /// Index into `STATES` and `TERRITORIES` by the name key of each code, name and other name,
+    /// with whether the entry is a territory.
+    static ref STATE_INDEX: HashMap<String, (&'static str, &'static str, bool)> = {
+        let mut index = HashMap::new();
+        let entries = STATES.iter().map(|e| (e, false)).chain(TERRITORIES.iter().map(|e| (e, true)));
+        for (&(code, name, other_names), is_territory) in entries {
+            for n in [code, name].iter().chain(other_names.iter()) {
+                index.insert(name_key(n), (code, name, is_territory));
+            }
+        }
+        index
+    };
+}
+
+/// How to output states.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum StateOutput {
+    /// USPS code, e.g. "CA".
+    Code,
+    /// Name, e.g. "California".
+    Name,
+}
+
+impl Default for StateOutput {
+    fn default() -> StateOutput {
+        StateOutput::Code
+    }
+}
+
+/// Normalize US states given by USPS code, name, AP style abbreviation or another common name,
+/// e.g. "Calif.", to their USPS code or name, rejecting unknown states.
+///
+/// Territories like Puerto Rico and the armed forces mail regions are accepted unless
+/// `states_only` is set. The District of Columbia is always accepted.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformers::StateTransformer;
+///
+/// let transformer = StateTransformer::new().states_only();
+/// assert_eq!(transformer.transform("calif", "State", 1), Ok(Some("CA".to_string())));
+/// assert_eq!(transformer.transform("Washington, D.C.", "State", 1), Ok(Some("DC".to_string())));
+/// assert_eq!(transformer.transform("Puerto Rico", "State", 1).unwrap_err().reason, "not a US state");
+/// assert_eq!(transformer.transform("Ontario", "State", 1).unwrap_err().reason, "not a valid US state");
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct StateTransformer {
+    #[serde(default)]
+    output: StateOutput,
+    /// Reject territories and the armed forces mail regions.
+    #[serde(default)]
+    states_only: bool,
+}
+
+impl StateTransformer {
+    pub fn new() -> StateTransformer {
+        StateTransformer {
+            output: Default::default(),
+            states_only: false,
+        }
+    }
+
+    pub fn with_output(self, output: StateOutput) -> StateTransformer {
+        StateTransformer {
+            output: output,
+            .. self
+        }
+    }
+
+    /// Reject territories and the armed forces mail regions, only accepting the states and the
+    /// District of Columbia.
+    pub fn states_only(self) -> StateTransformer {
+        StateTransformer {
+            states_only: true,
+            .. self
+        }
+    }
+}
+
+impl Transformer for StateTransformer {
+    fn transform(&self, field_value: &str, field_name: &str, record_n: usize) -> TransformResult {
+        match STATE_INDEX.get(&name_key(field_value)) {
+            Some(&(_, _, true)) if self.states_only => {
+                TransformResult::error(field_value, field_name, record_n, "not a US state")
+            },
+            Some(&(code, name, _)) => TransformResult::present(match self.output {
+                StateOutput::Code => code,
+                StateOutput::Name => name,
+            }),
+            None => TransformResult::error(field_value, field_name, record_n, "not a valid US state"),
+        }
+    }
+}
@@ -4,17 +4,136 @@ use transformer::{
     TransformResult
 };
 
+/// Which ends of fields to trim.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum TrimSide {
+    Both,
+    Left,
+    Right,
+}
+
+impl Default for TrimSide {
+    fn default() -> TrimSide {
+        TrimSide::Both
+    }
+}
+
+fn is_default_side(side: &TrimSide) -> bool {
+    *side == TrimSide::Both
+}
+
+/// Pairs of opening and closing quote characters stripped from around fields.
+static QUOTES: &'static [(char, char)] = &[('"', '"'), ('\'', '\''), ('“', '”'), ('‘', '’')];
+
+/// Trim whitespace, or a configurable set of characters, from fields.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformers::TrimTransformer;
+///
+/// let transformer = TrimTransformer::new().stripping_quotes().collapsing_whitespace();
+/// assert_eq!(transformer.transform(" \"Acme   Widgets \" ", "Company", 1), Ok(Some("Acme Widgets".to_string())));
+///
+/// let transformer = TrimTransformer::new().with_chars("*_ ");
+/// assert_eq!(transformer.transform("** Acme **", "Company", 1), Ok(Some("Acme".to_string())));
+/// ```
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
-pub struct TrimTransformer {}
+pub struct TrimTransformer {
+    /// Characters to trim instead of whitespace.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    chars: Option<String>,
+    #[serde(default, skip_serializing_if="is_default_side")]
+    side: TrimSide,
+    /// Strip a pair of quotes surrounding the trimmed field, trimming again inside them.
+    #[serde(default)]
+    strip_quotes: bool,
+    /// Replace runs of whitespace inside the field with a single space.
+    #[serde(default)]
+    collapse_whitespace: bool,
+}
 
 impl TrimTransformer {
     pub fn new() -> TrimTransformer {
-        TrimTransformer {}
+        TrimTransformer {
+            chars: None,
+            side: TrimSide::Both,
+            strip_quotes: false,
+            collapse_whitespace: false,
+        }
+    }
+
+    /// Trim the specified characters instead of whitespace.
+    pub fn with_chars(self, chars: &str) -> TrimTransformer {
+        TrimTransformer {
+            chars: Some(chars.to_string()),
+            .. self
+        }
+    }
+
+    pub fn with_side(self, side: TrimSide) -> TrimTransformer {
+        TrimTransformer {
+            side: side,
+            .. self
+        }
+    }
+
+    pub fn stripping_quotes(self) -> TrimTransformer {
+        TrimTransformer {
+            strip_quotes: true,
+            .. self
+        }
+    }
+
+    pub fn collapsing_whitespace(self) -> TrimTransformer {
+        TrimTransformer {
+            collapse_whitespace: true,
+            .. self
+        }
+    }
+
+    fn trim<'a>(&self, value: &'a str) -> &'a str {
+        let is_trimmed = |c: char| match self.chars {
+            Some(ref chars) => chars.contains(c),
+            None => c.is_whitespace(),
+        };
+        match self.side {
+            TrimSide::Both => value.trim_matches(is_trimmed),
+            TrimSide::Left => value.trim_start_matches(is_trimmed),
+            TrimSide::Right => value.trim_end_matches(is_trimmed),
+        }
     }
 }
 
 impl Transformer for TrimTransformer {
     fn transform(&self, field_value: &str, _: &str, _: usize) -> TransformResult {
-        TransformResult::present(field_value.trim())
+        let mut value = self.trim(field_value);
+        if self.strip_quotes {
+            let mut chars = value.chars();
+            let quoted = match (chars.next(), chars.next_back()) {
+                (Some(first), Some(last)) => QUOTES.iter().any(|&(open, close)| first == open && last == close),
+                _ => false,
+            };
+            if quoted {
+                let inner = chars.as_str();
+                value = self.trim(inner);
+            }
+        }
+        if self.collapse_whitespace {
+            let mut collapsed = String::with_capacity(value.len());
+            let mut in_whitespace = false;
+            for c in value.chars() {
+                if c.is_whitespace() {
+                    if !in_whitespace {
+                        collapsed.push(' ');
+                    }
+                    in_whitespace = true;
+                } else {
+                    collapsed.push(c);
+                    in_whitespace = false;
+                }
+            }
+            return TransformResult::present(&collapsed);
+        }
+        TransformResult::present(value)
     }
 }
@@ -7,9 +7,21 @@ use transformer::{
 use regex::Regex;
 
 lazy_static! {
-    static ref ZIP_REGEX: Regex = Regex::new(r"\A(\d{5})\D*(?:(\d{4}))?\z").unwrap();
+    static ref ZIP_REGEX: Regex = Regex::new(r"\A(\d{5})(?:\s*-?\s*(\d{4}))?\z").unwrap();
 }
 
+/// Validate US ZIP codes, formatting ZIP+4 codes like "12345-6789".
+///
+/// See `PostalCodeTransformer` for postal codes from other countries.
+///
+/// ```
+/// use csv_sanity::Transformer;
+/// use csv_sanity::transformers::ZipcodeTransformer;
+///
+/// let transformer = ZipcodeTransformer::new();
+/// assert_eq!(transformer.transform("12345 6789", "Zip", 1), Ok(Some("12345-6789".to_string())));
+/// assert!(transformer.transform("12345abc", "Zip", 1).is_err());
+/// ```
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
 pub struct ZipcodeTransformer { }
 
@@ -0,0 +1,59 @@
+//! Traits and types that define validations across several of a CSV record's fields.
+
+use transformer::TransformError;
+
+/// A transformed CSV record whose fields can be accessed by name.
+pub struct Record<'a>
+{
+    pub headers: &'a [String],
+    pub field_values: &'a [Option<String>],
+}
+
+impl<'a> Record<'a>
+{
+    pub fn new(headers: &'a [String], field_values: &'a [Option<String>]) -> Record<'a> {
+        Record {
+            headers: headers,
+            field_values: field_values,
+        }
+    }
+
+    /// Value of the field with the specified name, or `None` if the field is empty or missing.
+    ///
+    /// ```
+    /// use csv_sanity::validator::Record;
+    ///
+    /// let headers = vec!["country".to_string(), "state".to_string()];
+    /// let field_values = vec![Some("US".to_string()), None];
+    /// let record = Record::new(&headers, &field_values);
+    /// assert_eq!(record.get("country"), Some("US"));
+    /// assert_eq!(record.get("state"), None);
+    /// assert_eq!(record.get("zip"), None);
+    /// ```
+    pub fn get(&self, field_name: &str) -> Option<&'a str> {
+        self.headers.iter().position(|h| h == field_name)
+            .and_then(|field_n| self.field_values.get(field_n))
+            .and_then(|fv| fv.as_ref().map(|v| v.as_str()))
+    }
+
+    /// Construct one `TransformError` for each of the specified fields, all with the same reason.
+    pub fn errors(&self, field_names: &[&str], record_n: usize, reason: &str) -> Vec<TransformError> {
+        field_names.iter().map(|field_name| {
+            TransformError {
+                field_value: self.get(field_name).unwrap_or("").to_string(),
+                field_name: field_name.to_string(),
+                record_n: record_n,
+                reason: reason.to_string(),
+            }
+        }).collect()
+    }
+}
+
+pub trait Validator
+{
+    /// Names of the fields referenced by this validator.
+    fn field_names(&self) -> Vec<String>;
+
+    /// Validate a transformed record, returning any errors attributed to the offending fields.
+    fn validate(&self, record: &Record, record_n: usize) -> Vec<TransformError>;
+}
@@ -0,0 +1,116 @@
+use Validator;
+use validator::Record;
+use transformer::TransformError;
+
+use std::cmp::Ordering;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Operator
+{
+    LessThan,
+    LessThanOrEqual,
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+impl Operator
+{
+    fn accepts(&self, ordering: Ordering) -> bool {
+        use self::Operator::*;
+
+        match *self {
+            LessThan => ordering == Ordering::Less,
+            LessThanOrEqual => ordering != Ordering::Greater,
+            Equal => ordering == Ordering::Equal,
+            NotEqual => ordering != Ordering::Equal,
+            GreaterThan => ordering == Ordering::Greater,
+            GreaterThanOrEqual => ordering != Ordering::Less,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        use self::Operator::*;
+
+        match *self {
+            LessThan => "less than",
+            LessThanOrEqual => "less than or equal to",
+            Equal => "equal to",
+            NotEqual => "not equal to",
+            GreaterThan => "greater than",
+            GreaterThanOrEqual => "greater than or equal to",
+        }
+    }
+}
+
+/// How the values of two fields are compared.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Comparison
+{
+    /// Compare values as text, which also orders ISO 8601 dates and datetimes correctly.
+    Text,
+    /// Compare values as numbers.
+    Number,
+}
+
+impl Default for Comparison
+{
+    fn default() -> Comparison {
+        Comparison::Text
+    }
+}
+
+/// Compare the values of two fields of a record, e.g. to check that an end date is after a start
+/// date.
+///
+/// Records where either field is empty are not validated.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct CompareValidator
+{
+    left: String,
+    operator: Operator,
+    right: String,
+    #[serde(default)]
+    comparison: Comparison,
+}
+
+impl CompareValidator
+{
+    pub fn new(left: &str, operator: Operator, right: &str, comparison: Comparison) -> CompareValidator {
+        CompareValidator {
+            left: left.to_string(),
+            operator: operator,
+            right: right.to_string(),
+            comparison: comparison,
+        }
+    }
+}
+
+impl Validator for CompareValidator
+{
+    fn field_names(&self) -> Vec<String> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn validate(&self, record: &Record, record_n: usize) -> Vec<TransformError> {
+        let (left, right) = match (record.get(&self.left), record.get(&self.right)) {
+            (Some(l), Some(r)) => (l, r),
+            _ => return Vec::new(),
+        };
+        let field_names = [self.left.as_str(), self.right.as_str()];
+        let ordering = match self.comparison {
+            Comparison::Text => left.cmp(right),
+            Comparison::Number => match (left.parse::<f64>(), right.parse::<f64>()) {
+                (Ok(l), Ok(r)) => l.partial_cmp(&r).unwrap_or(Ordering::Equal),
+                _ => return record.errors(&field_names, record_n, "unable to compare as numbers"),
+            },
+        };
+        if self.operator.accepts(ordering) {
+            Vec::new()
+        } else {
+            let reason = format!("{} must be {} {}", self.left, self.operator.description(), self.right);
+            record.errors(&field_names, record_n, &reason)
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use Validator;
+use validator::Record;
+use transformer::TransformError;
+use validators::Condition;
+use newtypes::Regex;
+
+use regex;
+
+/// Require a field to match a pattern when a condition on another field is met.
+///
+/// Empty fields are not validated; use `RequiredIfValidator` to require them to be present.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct MatchIfValidator
+{
+    field_name: String,
+    regex: Regex,
+    when: Condition,
+}
+
+impl MatchIfValidator
+{
+    pub fn new(field_name: &str, regex: regex::Regex, when: Condition) -> MatchIfValidator {
+        MatchIfValidator {
+            field_name: field_name.to_string(),
+            regex: Regex::from(regex),
+            when: when,
+        }
+    }
+}
+
+impl Validator for MatchIfValidator
+{
+    fn field_names(&self) -> Vec<String> {
+        vec![self.field_name.clone(), self.when.field_name().to_string()]
+    }
+
+    fn validate(&self, record: &Record, record_n: usize) -> Vec<TransformError> {
+        match record.get(&self.field_name) {
+            Some(value) if !self.regex.is_match(value) && self.when.is_met(record) => {
+                let reason = format!("did not match pattern {} when {}", self.regex, self.when);
+                record.errors(&[&self.field_name, self.when.field_name()], record_n, &reason)
+            },
+            _ => Vec::new(),
+        }
+    }
+}
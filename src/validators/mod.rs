@@ -0,0 +1,106 @@
+use transformer::TransformError;
+use validator::{
+    Validator,
+    Record,
+};
+use newtypes::Regex;
+
+use regex;
+use std::fmt::{
+    self,
+    Formatter,
+    Display,
+};
+
+mod compare;
+pub use self::compare::{
+    CompareValidator,
+    Comparison,
+    Operator,
+};
+
+mod required_if;
+pub use self::required_if::RequiredIfValidator;
+
+mod match_if;
+pub use self::match_if::MatchIfValidator;
+
+/// A condition on the value of one of a record's fields.
+///
+/// Empty fields are matched as empty strings.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Condition
+{
+    field_name: String,
+    regex: Regex,
+    #[serde(default)]
+    negate: bool,
+}
+
+impl Condition
+{
+    pub fn matching(field_name: &str, regex: regex::Regex) -> Condition {
+        Condition {
+            field_name: field_name.to_string(),
+            regex: Regex::from(regex),
+            negate: false,
+        }
+    }
+
+    pub fn not_matching(field_name: &str, regex: regex::Regex) -> Condition {
+        Condition {
+            negate: true,
+            .. Self::matching(field_name, regex)
+        }
+    }
+
+    pub fn field_name(&self) -> &str {
+        &self.field_name
+    }
+
+    pub fn is_met(&self, record: &Record) -> bool {
+        let is_match = self.regex.is_match(record.get(&self.field_name).unwrap_or(""));
+        is_match != self.negate
+    }
+}
+
+impl Display for Condition
+{
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if self.negate {
+            write!(formatter, "{} does not match pattern {}", self.field_name, self.regex)
+        } else {
+            write!(formatter, "{} matches pattern {}", self.field_name, self.regex)
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub enum Validators
+{
+    Compare(CompareValidator),
+    RequiredIf(RequiredIfValidator),
+    MatchIf(MatchIfValidator),
+}
+
+impl Validator for Validators {
+    fn field_names(&self) -> Vec<String> {
+        use self::Validators::*;
+
+        match *self {
+            Compare(ref v) => v.field_names(),
+            RequiredIf(ref v) => v.field_names(),
+            MatchIf(ref v) => v.field_names(),
+        }
+    }
+
+    fn validate(&self, record: &Record, record_n: usize) -> Vec<TransformError> {
+        use self::Validators::*;
+
+        match *self {
+            Compare(ref v) => v.validate(record, record_n),
+            RequiredIf(ref v) => v.validate(record, record_n),
+            MatchIf(ref v) => v.validate(record, record_n),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use Validator;
+use validator::Record;
+use transformer::TransformError;
+use validators::Condition;
+
+/// Require a field to be present when a condition on another field is met.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+pub struct RequiredIfValidator
+{
+    field_name: String,
+    when: Condition,
+}
+
+impl RequiredIfValidator
+{
+    pub fn new(field_name: &str, when: Condition) -> RequiredIfValidator {
+        RequiredIfValidator {
+            field_name: field_name.to_string(),
+            when: when,
+        }
+    }
+}
+
+impl Validator for RequiredIfValidator
+{
+    fn field_names(&self) -> Vec<String> {
+        vec![self.field_name.clone(), self.when.field_name().to_string()]
+    }
+
+    fn validate(&self, record: &Record, record_n: usize) -> Vec<TransformError> {
+        if record.get(&self.field_name).is_none() && self.when.is_met(record) {
+            record.errors(&[&self.field_name], record_n, &format!("required when {}", self.when))
+        } else {
+            Vec::new()
+        }
+    }
+}